@@ -7,15 +7,9 @@ const {
   TransactionInstruction,
   sendAndConfirmTransaction,
 } = require('@solana/web3.js');
-const {
-  struct,
-  u8,
-  seq,
-  blob
-} = require('@solana/buffer-layout');
 const { Buffer } = require('buffer');
+const { createHash } = require('crypto');
 const BN = require('bn.js');
-const fetch = require('node-fetch');
 
 // Connection to the Solana cluster
 const connection = new Connection('https://api.devnet.solana.com', 'confirmed');
@@ -23,107 +17,215 @@ const connection = new Connection('https://api.devnet.solana.com', 'confirmed');
 // Program ID (deployed program ID)
 const programId = new PublicKey('BLFfy2mhNyhwHB135oux43d4EtffJsmJ4LxSX66e7tHk');
 
-// Fee recipient address
-const FEE_ADDRESS = new PublicKey('BAGek78CDYQ8phuDqNk7sQzD7LdJeKkb7jD4y2AyR3tJ');
+// Pyth SOL/USD price account the program reads fees from
+// (mainnet: H6ARHf6YXhGYeQfUzQNGk6rDNnLBQKrenN712K4AQJEG)
+const SOL_USD_PRICE_ACCOUNT = new PublicKey('J83w4HKfqxwcq3BEMMkPFSppX3gqekLyLJBexebFVkix');
 
-// Function to fetch current SOL price
-async function getSolPrice() {
-  try {
-    // Using CoinGecko API to get SOL price
-    const response = await fetch('https://api.coingecko.com/api/v3/simple/price?ids=solana&vs_currencies=usd');
-    const data = await response.json();
-    const solPriceUsd = data.solana.usd;
-    
-    console.log(`Current SOL price: $${solPriceUsd}`);
-    
-    // Convert to cents and return as integer (e.g., $100.50 => 10050)
-    return Math.round(solPriceUsd * 100);
-  } catch (error) {
-    console.error('Error fetching SOL price:', error);
-    throw new Error('Failed to fetch SOL price. Please try again.');
-  }
+// SPL programs used for governance tokens
+const TOKEN_PROGRAM_ID = new PublicKey('TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA');
+const ASSOCIATED_TOKEN_PROGRAM_ID = new PublicKey('ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL');
+
+// Instruction indexes, in the order of the program's `DaoInstruction` enum
+const Instruction = {
+  CreateDao: 0,
+  CreateProposal: 1,
+  Vote: 2,
+  Featured: 3,
+  Modules: 4,
+  FinalizeProposal: 5,
+  RenewFeatured: 12,
+  AddPodMember: 21,
+  RemovePodMember: 22,
+  ReleaseVoteTokens: 26,
+};
+
+// Module types, in the order of the program's `ModuleType` enum
+const ModuleType = {
+  POD: 0,
+  POL: 1,
+};
+
+// PDA helpers, matching the program's `pda` module
+
+// Long names are hashed so they fit in a seed
+function nameSeed(name) {
+  return createHash('sha256').update(name, 'utf8').digest();
 }
 
-// Utility function to create a new account
-async function createAccount(connection, payer, space) {
-  const newAccount = Keypair.generate();
-  const transaction = new Transaction().add(
-    SystemProgram.createAccount({
-      fromPubkey: payer.publicKey,
-      newAccountPubkey: newAccount.publicKey,
-      lamports: await connection.getMinimumBalanceForRentExemption(space),
-      space,
-      programId,
-    })
-  );
+function findDaoAddress(authority, name) {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from('dao'), authority.toBuffer(), nameSeed(name)],
+    programId
+  )[0];
+}
+
+function findProposalAddress(dao, authority, name) {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from('proposal'), dao.toBuffer(), authority.toBuffer(), nameSeed(name)],
+    programId
+  )[0];
+}
+
+function findVoteAddress(proposal, voter) {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from('vote'), proposal.toBuffer(), voter.toBuffer()],
+    programId
+  )[0];
+}
+
+function findFeaturedAddress(dao) {
+  return PublicKey.findProgramAddressSync([Buffer.from('featured'), dao.toBuffer()], programId)[0];
+}
+
+function findModuleAddress(dao) {
+  return PublicKey.findProgramAddressSync([Buffer.from('module'), dao.toBuffer()], programId)[0];
+}
+
+function findPodAddress(dao, name) {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from('pod'), dao.toBuffer(), nameSeed(name)],
+    programId
+  )[0];
+}
+
+function findTreasuryAddress(dao) {
+  return PublicKey.findProgramAddressSync([Buffer.from('treasury'), dao.toBuffer()], programId)[0];
+}
+
+function findConfigAddress() {
+  return PublicKey.findProgramAddressSync([Buffer.from('config')], programId)[0];
+}
+
+function findReputationAddress(dao, member) {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from('reputation'), dao.toBuffer(), member.toBuffer()],
+    programId
+  )[0];
+}
+
+function findAssociatedTokenAddress(owner, mint) {
+  return PublicKey.findProgramAddressSync(
+    [owner.toBuffer(), TOKEN_PROGRAM_ID.toBuffer(), mint.toBuffer()],
+    ASSOCIATED_TOKEN_PROGRAM_ID
+  )[0];
+}
+
+// Borsh serialization of the instruction arguments
+
+function serializeU8(value) {
+  return Buffer.from([value]);
+}
+
+function serializeU16(value) {
+  const buf = Buffer.alloc(2);
+  buf.writeUInt16LE(value, 0);
+  return buf;
+}
+
+function serializeU64(value) {
+  return new BN(value.toString()).toArrayLike(Buffer, 'le', 8);
+}
+
+function serializeI64(value) {
+  return new BN(value.toString()).toTwos(64).toArrayLike(Buffer, 'le', 8);
+}
 
-  await sendAndConfirmTransaction(connection, transaction, [payer, newAccount]);
-  return newAccount;
+function serializeBool(value) {
+  return Buffer.from([value ? 1 : 0]);
 }
 
-// Simpler approach - manual serialization for instruction data
 function serializeString(str) {
-  const buf = Buffer.alloc(4 + str.length);
-  buf.writeUInt32LE(str.length, 0);
-  buf.write(str, 4);
+  const bytes = Buffer.from(str, 'utf8');
+  return Buffer.concat([serializeU32(bytes.length), bytes]);
+}
+
+function serializeU32(value) {
+  const buf = Buffer.alloc(4);
+  buf.writeUInt32LE(value, 0);
   return buf;
 }
 
+function serializePubkey(pubkey) {
+  return new PublicKey(pubkey).toBuffer();
+}
+
+function serializeOption(value, serialize) {
+  if (value === null || value === undefined) {
+    return Buffer.from([0]);
+  }
+  return Buffer.concat([Buffer.from([1]), serialize(value)]);
+}
+
+function serializeVec(items, serialize) {
+  return Buffer.concat([serializeU32(items.length), ...items.map(serialize)]);
+}
+
+// Voting modes, serialized like the program's `VotingMode` enum
+const VotingMode = {
+  token: () => Buffer.from([0]),
+  reputation: (maxWeight = null) =>
+    Buffer.concat([Buffer.from([1]), serializeOption(maxWeight, serializeU64)]),
+  quadratic: (voiceCredits) => Buffer.concat([Buffer.from([2]), serializeU64(voiceCredits)]),
+};
+
+// Ballots, serialized like the program's `VoteChoice` enum
+const VoteChoice = {
+  option: (index) => Buffer.from([0, index]), // 0 is "For" and 1 "Against" on yes/no proposals
+  abstain: () => Buffer.from([1]),
+  ranking: (indexes) => Buffer.concat([Buffer.from([2]), serializeVec(indexes, serializeU8)]),
+  credits: (allocations) =>
+    Buffer.concat([
+      Buffer.from([3]),
+      serializeVec(allocations, ({ option, credits }) =>
+        Buffer.concat([serializeU8(option), serializeU64(credits)])
+      ),
+    ]),
+};
+
+// Governance rules of a new DAO, see `GovernanceConfig`
+function serializeGovernanceConfig({
+  quorum = 1,
+  approvalThresholdBps = 5_000,
+  executionDelay = 0,
+  votingMode = VotingMode.token(),
+}) {
+  return Buffer.concat([
+    serializeU64(quorum),
+    serializeU16(approvalThresholdBps),
+    serializeI64(executionDelay),
+    votingMode,
+  ]);
+}
+
 function serializeCreateDaoInstruction(
-  name, 
-  description, 
-  discordServer, 
-  twitter, 
-  telegram, 
-  instagram, 
-  tiktok, 
-  website, 
-  treasury, 
-  profile, 
-  token_address,
-  solPriceUsd
+  name,
+  description,
+  discordServer,
+  twitter,
+  telegram,
+  instagram,
+  tiktok,
+  website,
+  profile,
+  tokenAddress,
+  governance,
+  referrer
 ) {
-  // Instruction index (0 for CreateDao)
-  const instructionBuf = Buffer.alloc(1);
-  instructionBuf.writeUInt8(0, 0);
-  
-  // Serialize all strings
-  const nameBuf = serializeString(name);
-  const descriptionBuf = serializeString(description);
-  const discordServerBuf = serializeString(discordServer);
-  const twitterBuf = serializeString(twitter);
-  const telegramBuf = serializeString(telegram);
-  const instagramBuf = serializeString(instagram);
-  const tiktokBuf = serializeString(tiktok);
-  const websiteBuf = serializeString(website);
-  const treasuryBuf = serializeString(treasury);
-  const profileBuf = serializeString(profile);
-  const tokenAddressBuf = serializeString(token_address);
-  
-  // Serialize u64 sol price (8 bytes, little-endian)
-  const solPriceBuf = Buffer.alloc(8);
-  
-  // Convert to u64 (BN)
-  const solPriceBN = new BN(solPriceUsd.toString());
-  solPriceBN.toArray('le', 8).forEach((byte, index) => {
-    solPriceBuf[index] = byte;
-  });
-  
-  // Concat all buffers
   return Buffer.concat([
-    instructionBuf,
-    nameBuf,
-    descriptionBuf,
-    discordServerBuf,
-    twitterBuf,
-    telegramBuf,
-    instagramBuf,
-    tiktokBuf,
-    websiteBuf,
-    treasuryBuf,
-    profileBuf,
-    tokenAddressBuf,
-    solPriceBuf
+    serializeU8(Instruction.CreateDao),
+    serializeString(name),
+    serializeString(description),
+    serializeString(discordServer),
+    serializeString(twitter),
+    serializeString(telegram),
+    serializeString(instagram),
+    serializeString(tiktok),
+    serializeString(website),
+    serializeString(profile),
+    serializeString(tokenAddress),
+    serializeGovernanceConfig(governance),
+    serializeOption(null, serializePubkey), // fee_mint: pay the fee in SOL
+    serializeOption(referrer, serializePubkey),
   ]);
 }
 
@@ -133,131 +235,148 @@ function serializeCreateProposalInstruction(
   daoId,
   podId,
   startTime,
-  endTime
+  endTime,
+  options,
+  ranked
 ) {
-  // Instruction index (1 for CreateProposal)
-  const instructionBuf = Buffer.alloc(1);
-  instructionBuf.writeUInt8(1, 0);
-  
-  // Serialize all strings
-  const nameBuf = serializeString(name);
-  const descriptionBuf = serializeString(description);
-  const daoIdBuf = serializeString(daoId);
-  const podIdBuf = serializeString(podId);
-  
-  // Serialize i64 timestamps (8 bytes each, little-endian)
-  const startTimeBuf = Buffer.alloc(8);
-  const endTimeBuf = Buffer.alloc(8);
-  
-  const startTimeBN = new BN(startTime.toString());
-  const endTimeBN = new BN(endTime.toString());
-  
-  startTimeBN.toArray('le', 8).forEach((byte, index) => {
-    startTimeBuf[index] = byte;
-  });
-  
-  endTimeBN.toArray('le', 8).forEach((byte, index) => {
-    endTimeBuf[index] = byte;
-  });
-  
-  // Concat all buffers
   return Buffer.concat([
-    instructionBuf,
-    nameBuf,
-    descriptionBuf,
-    daoIdBuf,
-    podIdBuf,
-    startTimeBuf,
-    endTimeBuf
+    serializeU8(Instruction.CreateProposal),
+    serializeString(name),
+    serializeString(description),
+    serializePubkey(daoId),
+    serializeString(podId),
+    serializeI64(startTime),
+    serializeI64(endTime),
+    serializeU8(0), // withdrawal: none
+    serializeU32(0), // instructions: none
+    serializeVec(options, serializeString),
+    serializeBool(ranked),
   ]);
 }
 
-function serializeVoteInstruction(
-  voteValue,
-  proposalId
-) {
-  // Instruction index (2 for Vote)
-  const instructionBuf = Buffer.alloc(1);
-  instructionBuf.writeUInt8(2, 0);
-  
-  // Serialize strings
-  const voteBuf = serializeString(voteValue);
-  const proposalIdBuf = serializeString(proposalId);
-  
-  // Concat all buffers
+function serializeVoteInstruction(choice, proposalId) {
   return Buffer.concat([
-    instructionBuf,
-    voteBuf,
-    proposalIdBuf
+    serializeU8(Instruction.Vote),
+    choice,
+    serializeString(proposalId),
   ]);
 }
 
-function serializeFeaturedInstruction(
-  daoId,
-  days,
-  solPriceUsd
-) {
-  // Instruction index (3 for Featured)
-  const instructionBuf = Buffer.alloc(1);
-  instructionBuf.writeUInt8(3, 0);
-  
-  // Serialize string
-  const daoIdBuf = serializeString(daoId);
-  
-  // Serialize u64 days (8 bytes, little-endian)
-  const daysBuf = Buffer.alloc(8);
-  const daysBN = new BN(days.toString());
-  daysBN.toArray('le', 8).forEach((byte, index) => {
-    daysBuf[index] = byte;
-  });
-  
-  // Serialize u64 sol price (8 bytes, little-endian)
-  const solPriceBuf = Buffer.alloc(8);
-  
-  // Convert to u64 (BN)
-  const solPriceBN = new BN(solPriceUsd.toString());
-  solPriceBN.toArray('le', 8).forEach((byte, index) => {
-    solPriceBuf[index] = byte;
-  });
-  
-  // Concat all buffers
+function serializeFeaturedInstruction(instruction, daoId, days) {
   return Buffer.concat([
-    instructionBuf,
-    daoIdBuf,
-    daysBuf,
-    solPriceBuf
+    serializeU8(instruction),
+    serializePubkey(daoId),
+    serializeU64(days),
+    serializeOption(null, serializePubkey), // fee_mint: pay the fee in SOL
   ]);
 }
 
-function serializeModulesInstruction(
-  daoId,
-  moduleType,
-  solPriceUsd
-) {
-  // Instruction index (4 for Modules)
-  const instructionBuf = Buffer.alloc(1);
-  instructionBuf.writeUInt8(4, 0);
+function serializeModulesInstruction(daoId, moduleType) {
+  return Buffer.concat([
+    serializeU8(Instruction.Modules),
+    serializePubkey(daoId),
+    serializeU8(moduleType),
+    serializeOption(null, serializePubkey), // fee_mint: pay the fee in SOL
+  ]);
+}
+
+// Minimal Borsh reader for the account fields the client needs
+class AccountReader {
+  constructor(data) {
+    this.data = data;
+    this.offset = 0;
+  }
   
-  // Serialize strings
-  const daoIdBuf = serializeString(daoId);
-  const moduleTypeBuf = serializeString(moduleType);
+  u8() {
+    return this.data.readUInt8(this.offset++);
+  }
   
-  // Serialize u64 sol price (8 bytes, little-endian)
-  const solPriceBuf = Buffer.alloc(8);
+  u16() {
+    const value = this.data.readUInt16LE(this.offset);
+    this.offset += 2;
+    return value;
+  }
   
-  // Convert to u64 (BN)
-  const solPriceBN = new BN(solPriceUsd.toString());
-  solPriceBN.toArray('le', 8).forEach((byte, index) => {
-    solPriceBuf[index] = byte;
-  });
+  u64() {
+    const value = new BN(this.data.subarray(this.offset, this.offset + 8), 'le');
+    this.offset += 8;
+    return value;
+  }
   
-  // Concat all buffers
-  return Buffer.concat([
-    instructionBuf,
-    daoIdBuf,
-    moduleTypeBuf,
-    solPriceBuf
-  ]);
+  pubkey() {
+    const value = new PublicKey(this.data.subarray(this.offset, this.offset + 32));
+    this.offset += 32;
+    return value;
+  }
+  
+  string() {
+    const length = this.data.readUInt32LE(this.offset);
+    const value = this.data.toString('utf8', this.offset + 4, this.offset + 4 + length);
+    this.offset += 4 + length;
+    return value;
+  }
+}
+
+async function fetchAccountData(pubkey) {
+  const accountInfo = await connection.getAccountInfo(pubkey);
+  if (accountInfo === null) {
+    throw new Error(`Account ${pubkey.toString()} not found`);
+  }
+  return accountInfo.data;
+}
+
+// Read the fee recipient and fees (in USD cents) from the global config
+async function fetchConfig() {
+  const reader = new AccountReader(await fetchAccountData(findConfigAddress()));
+  reader.u8(); // account_type
+  const admin = reader.pubkey();
+  const feeRecipient = reader.pubkey();
+  const fees = {
+    createDao: reader.u64(),
+    featuredPerDay: reader.u64(),
+    podModule: reader.u64(),
+    polModule: reader.u64(),
+    createProposal: reader.u64(),
+    vote: reader.u64(),
+  };
+  return { admin, feeRecipient, fees };
+}
+
+// Read the governance token and voting mode of a DAO
+async function fetchDao(daoId) {
+  const reader = new AccountReader(await fetchAccountData(new PublicKey(daoId)));
+  reader.u8(); // account_type
+  const authority = reader.pubkey();
+  const name = reader.string();
+  for (let i = 0; i < 7; i++) {
+    reader.string(); // description, discord_server, twitter, telegram, instagram, tiktok, website
+  }
+  const treasury = reader.pubkey();
+  reader.string(); // profile
+  const tokenAddress = reader.string();
+  reader.u64(); // governance.quorum
+  reader.u16(); // governance.approval_threshold_bps
+  reader.u64(); // governance.execution_delay
+  const votingMode = ['token', 'reputation', 'quadratic'][reader.u8()];
+  
+  let governanceMint = null;
+  try {
+    governanceMint = tokenAddress ? new PublicKey(tokenAddress) : null;
+  } catch (error) {
+    // The program ignores token addresses that aren't valid public keys
+  }
+  return { authority, name, treasury, governanceMint, votingMode };
+}
+
+// Fee recipient and price accounts, only needed when the config charges the fee
+function feeAccounts(config, fee) {
+  if (fee.isZero()) {
+    return [];
+  }
+  return [
+    { pubkey: config.feeRecipient, isSigner: false, isWritable: true },
+    { pubkey: SOL_USD_PRICE_ACCOUNT, isSigner: false, isWritable: false },
+  ];
 }
 
 // Create a new DAO
@@ -271,27 +390,16 @@ async function createDao(
   instagram,
   tiktok,
   website,
-  treasury,
   profile,
-  token_address,
-  sol_price_usd = null // Optional parameter for direct price input
+  tokenAddress = '', // Governance token mint, or empty for one vote per wallet
+  governance = {}, // Quorum, approval threshold, execution delay and voting mode
+  referrer = null // Wallet receiving the config's referral share of the fee
 ) {
-  // Get current SOL price if not provided
-  if (sol_price_usd === null) {
-    sol_price_usd = await getSolPrice();
-  }
-  console.log(`Creating DAO with SOL price: $${sol_price_usd/100} (${sol_price_usd} cents)`);
+  const config = await fetchConfig();
+  console.log(`Creating DAO, fee: $${config.fees.createDao.toNumber() / 100} in SOL`);
   
-  // Calculate expected fee based on SOL price
-  const expectedFeeInSol = 20 / (sol_price_usd / 100);
-  const expectedFeeInLamports = Math.round(expectedFeeInSol * 1_000_000_000);
-  console.log(`Expected fee: ${expectedFeeInSol} SOL (${expectedFeeInLamports} lamports)`);
-  
-  // Generate a new keypair for the DAO
-  const daoAccount = Keypair.generate();
-  console.log('Generated DAO account:', daoAccount.publicKey.toString());
-  
-  console.log('Serializing DAO instruction with token_address:', token_address);
+  const daoAccount = findDaoAddress(payer.publicKey, name);
+  console.log('DAO account:', daoAccount.toString());
   
   // Serialize instruction data
   const data = serializeCreateDaoInstruction(
@@ -303,43 +411,37 @@ async function createDao(
     instagram,
     tiktok,
     website,
-    treasury,
     profile,
-    token_address,
-    sol_price_usd
+    tokenAddress,
+    governance,
+    referrer
   );
   
-  // Create instruction
-  const instruction = new TransactionInstruction({
-    keys: [
-      { pubkey: payer.publicKey, isSigner: true, isWritable: true },
-      { pubkey: daoAccount.publicKey, isSigner: true, isWritable: true },
-      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
-      { pubkey: FEE_ADDRESS, isSigner: false, isWritable: true },
-    ],
-    programId,
-    data,
-  });
+  const keys = [
+    { pubkey: payer.publicKey, isSigner: true, isWritable: true },
+    { pubkey: daoAccount, isSigner: false, isWritable: true },
+    { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+    { pubkey: config.feeRecipient, isSigner: false, isWritable: true },
+    { pubkey: findTreasuryAddress(daoAccount), isSigner: false, isWritable: true },
+    { pubkey: SOL_USD_PRICE_ACCOUNT, isSigner: false, isWritable: false },
+    { pubkey: findConfigAddress(), isSigner: false, isWritable: false },
+  ];
+  if (referrer !== null) {
+    keys.push({ pubkey: new PublicKey(referrer), isSigner: false, isWritable: true });
+  }
   
-  // Create transaction
-  const transaction = new Transaction().add(instruction);
+  const transaction = new Transaction().add(
+    new TransactionInstruction({ keys, programId, data })
+  );
   
   console.log('Sending transaction...');
-  // Sign transaction with both payer and the new DAO account keypair
-  const signature = await sendAndConfirmTransaction(
-    connection, 
-    transaction, 
-    [payer, daoAccount],
-    {
-      skipPreflight: true, // Skip the preflight check to debug issues
-    }
-  );
+  const signature = await sendAndConfirmTransaction(connection, transaction, [payer]);
   
   console.log('Transaction signature:', signature);
   console.log('DAO created successfully!');
-  console.log('DAO ID:', daoAccount.publicKey.toString());
+  console.log('DAO ID:', daoAccount.toString());
   
-  return daoAccount.publicKey;
+  return daoAccount;
 }
 
 // Create a new proposal
@@ -350,11 +452,14 @@ async function createProposal(
   daoId,
   podId,
   startTime,
-  endTime
+  endTime,
+  options = [], // Labels of a multiple choice proposal, or empty for "For"/"Against"
+  ranked = false // Voters rank the options, decided by instant runoff
 ) {
-  // Generate a new keypair for the proposal
-  const proposalAccount = Keypair.generate();
-  console.log('Generated proposal account:', proposalAccount.publicKey.toString());
+  const config = await fetchConfig();
+  const dao = new PublicKey(daoId);
+  const proposalAccount = findProposalAddress(dao, payer.publicKey, name);
+  console.log('Proposal account:', proposalAccount.toString());
   
   // Serialize instruction data
   const data = serializeCreateProposalInstruction(
@@ -363,223 +468,291 @@ async function createProposal(
     daoId,
     podId,
     startTime,
-    endTime
+    endTime,
+    options,
+    ranked
   );
   
-  // Create instruction
-  const instruction = new TransactionInstruction({
-    keys: [
-      { pubkey: payer.publicKey, isSigner: true, isWritable: true },
-      { pubkey: proposalAccount.publicKey, isSigner: true, isWritable: true },
-      { pubkey: new PublicKey(daoId), isSigner: false, isWritable: false },
-      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
-      { pubkey: FEE_ADDRESS, isSigner: false, isWritable: true },
-    ],
-    programId,
-    data,
-  });
+  const keys = [
+    { pubkey: payer.publicKey, isSigner: true, isWritable: true },
+    { pubkey: proposalAccount, isSigner: false, isWritable: true },
+    { pubkey: dao, isSigner: false, isWritable: false },
+    { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+    { pubkey: findConfigAddress(), isSigner: false, isWritable: false },
+  ];
+  
+  // Pod proposals need the pod and the module registry, to check the POD module is enabled
+  if (podId !== '') {
+    keys.push(
+      { pubkey: new PublicKey(podId), isSigner: false, isWritable: false },
+      { pubkey: findModuleAddress(dao), isSigner: false, isWritable: false }
+    );
+  }
+  keys.push(...feeAccounts(config, config.fees.createProposal));
   
-  // Create transaction
-  const transaction = new Transaction().add(instruction);
+  const transaction = new Transaction().add(
+    new TransactionInstruction({ keys, programId, data })
+  );
   
   console.log('Sending transaction...');
-  // Sign transaction with both payer and the new proposal account keypair
-  const signature = await sendAndConfirmTransaction(
-    connection, 
-    transaction, 
-    [payer, proposalAccount],
-    {
-      skipPreflight: true, // Skip the preflight check to debug issues
-    }
-  );
+  const signature = await sendAndConfirmTransaction(connection, transaction, [payer]);
   
   console.log('Transaction signature:', signature);
   console.log('Proposal created successfully!');
-  console.log('Proposal ID:', proposalAccount.publicKey.toString());
+  console.log('Proposal ID:', proposalAccount.toString());
   
-  return proposalAccount.publicKey;
+  return proposalAccount;
 }
 
-// Vote on a proposal
+// Vote on a proposal of a DAO
 async function vote(
   payer,
-  voteValue,
-  proposalId
+  daoId,
+  proposalId,
+  choice // A `VoteChoice`, e.g. VoteChoice.option(0) for "For"
 ) {
-  // Generate a new keypair for the vote
-  const voteAccount = Keypair.generate();
-  console.log('Generated vote account:', voteAccount.publicKey.toString());
+  const config = await fetchConfig();
+  const dao = new PublicKey(daoId);
+  const proposal = new PublicKey(proposalId);
+  const { governanceMint, votingMode } = await fetchDao(dao);
+  const voteAccount = findVoteAddress(proposal, payer.publicKey);
+  console.log('Vote account:', voteAccount.toString());
   
   // Serialize instruction data
-  const data = serializeVoteInstruction(
-    voteValue,
-    proposalId
+  const data = serializeVoteInstruction(choice, proposal.toString());
+  
+  const keys = [
+    { pubkey: payer.publicKey, isSigner: true, isWritable: true },
+    { pubkey: voteAccount, isSigner: false, isWritable: true },
+    { pubkey: proposal, isSigner: false, isWritable: true },
+    { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+    { pubkey: dao, isSigner: false, isWritable: false },
+    { pubkey: findConfigAddress(), isSigner: false, isWritable: false },
+  ];
+  
+  // Reputation votes read the voter's reputation, token votes escrow the voter's balance
+  if (votingMode === 'reputation') {
+    keys.push(
+      { pubkey: findModuleAddress(dao), isSigner: false, isWritable: false },
+      {
+        pubkey: findReputationAddress(dao, payer.publicKey),
+        isSigner: false,
+        isWritable: false,
+      }
+    );
+  } else if (governanceMint !== null) {
+    keys.push(
+      {
+        pubkey: findAssociatedTokenAddress(payer.publicKey, governanceMint),
+        isSigner: false,
+        isWritable: true,
+      },
+      {
+        pubkey: findAssociatedTokenAddress(voteAccount, governanceMint),
+        isSigner: false,
+        isWritable: true,
+      },
+      { pubkey: governanceMint, isSigner: false, isWritable: false },
+      { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+      { pubkey: ASSOCIATED_TOKEN_PROGRAM_ID, isSigner: false, isWritable: false }
+    );
+  }
+  keys.push(...feeAccounts(config, config.fees.vote));
+  
+  const transaction = new Transaction().add(
+    new TransactionInstruction({ keys, programId, data })
   );
   
-  // Create instruction
+  console.log('Sending transaction...');
+  const signature = await sendAndConfirmTransaction(connection, transaction, [payer]);
+  
+  console.log('Transaction signature:', signature);
+  console.log('Vote recorded successfully!');
+  console.log('Vote ID:', voteAccount.toString());
+  
+  return voteAccount;
+}
+
+// Finalize a proposal once its voting period is over
+async function finalizeProposal(payer, daoId, proposalId) {
   const instruction = new TransactionInstruction({
     keys: [
-      { pubkey: payer.publicKey, isSigner: true, isWritable: true },
-      { pubkey: voteAccount.publicKey, isSigner: true, isWritable: true },
-      { pubkey: new PublicKey(proposalId), isSigner: false, isWritable: false },
-      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
-      { pubkey: FEE_ADDRESS, isSigner: false, isWritable: true },
+      { pubkey: new PublicKey(proposalId), isSigner: false, isWritable: true },
+      { pubkey: new PublicKey(daoId), isSigner: false, isWritable: false },
     ],
     programId,
-    data,
+    data: serializeU8(Instruction.FinalizeProposal),
   });
   
-  // Create transaction
   const transaction = new Transaction().add(instruction);
-  
-  console.log('Sending transaction...');
-  // Sign transaction with both payer and the new vote account keypair
-  const signature = await sendAndConfirmTransaction(
-    connection, 
-    transaction, 
-    [payer, voteAccount],
-    {
-      skipPreflight: true, // Skip the preflight check to debug issues
-    }
-  );
+  const signature = await sendAndConfirmTransaction(connection, transaction, [payer]);
   
   console.log('Transaction signature:', signature);
-  console.log('Vote recorded successfully!');
-  console.log('Vote ID:', voteAccount.publicKey.toString());
-  
-  return voteAccount.publicKey;
+  console.log('Proposal finalized successfully!');
 }
 
-// Create a featured listing for a DAO
-async function createFeatured(
-  payer,
-  daoId,
-  days = 1, // Number of days to feature the DAO (default: 1 day)
-  sol_price_usd = null // Optional parameter for direct price input
-) {
-  // Get current SOL price if not provided
-  if (sol_price_usd === null) {
-    sol_price_usd = await getSolPrice();
+// Return the governance tokens escrowed by a vote once the proposal is finalized
+async function releaseVoteTokens(payer, daoId, proposalId, voter = payer.publicKey) {
+  const proposal = new PublicKey(proposalId);
+  const { governanceMint } = await fetchDao(daoId);
+  if (governanceMint === null) {
+    throw new Error('The DAO has no governance token, so votes escrow nothing.');
   }
-  console.log(`Creating Featured listing for ${days} days with SOL price: $${sol_price_usd/100} (${sol_price_usd} cents)`);
-  
-  // Calculate expected fee based on SOL price and days
-  const totalUsdFee = 20 * days; // $20 per day
-  const expectedFeeInSol = totalUsdFee / (sol_price_usd / 100);
-  const expectedFeeInLamports = Math.round(expectedFeeInSol * 1_000_000_000);
-  console.log(`Expected fee: $${totalUsdFee} for ${days} days = ${expectedFeeInSol} SOL (${expectedFeeInLamports} lamports)`);
+  const voteAccount = findVoteAddress(proposal, voter);
   
-  // Generate a new keypair for the Featured account
-  const featuredAccount = Keypair.generate();
-  console.log('Generated Featured account:', featuredAccount.publicKey.toString());
-  
-  // Serialize instruction data
-  const data = serializeFeaturedInstruction(
-    daoId,
-    days,
-    sol_price_usd
-  );
-  
-  // Create instruction
   const instruction = new TransactionInstruction({
     keys: [
-      { pubkey: payer.publicKey, isSigner: true, isWritable: true },
-      { pubkey: featuredAccount.publicKey, isSigner: true, isWritable: true },
-      { pubkey: new PublicKey(daoId), isSigner: false, isWritable: false },
-      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
-      { pubkey: FEE_ADDRESS, isSigner: false, isWritable: true },
+      { pubkey: voter, isSigner: false, isWritable: true },
+      { pubkey: voteAccount, isSigner: false, isWritable: true },
+      { pubkey: proposal, isSigner: false, isWritable: false },
+      {
+        pubkey: findAssociatedTokenAddress(voteAccount, governanceMint),
+        isSigner: false,
+        isWritable: true,
+      },
+      {
+        pubkey: findAssociatedTokenAddress(voter, governanceMint),
+        isSigner: false,
+        isWritable: true,
+      },
+      { pubkey: governanceMint, isSigner: false, isWritable: false },
+      { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
     ],
     programId,
-    data,
+    data: serializeU8(Instruction.ReleaseVoteTokens),
   });
   
-  // Create transaction
   const transaction = new Transaction().add(instruction);
+  const signature = await sendAndConfirmTransaction(connection, transaction, [payer]);
   
-  console.log('Sending transaction...');
-  // Sign transaction with both payer and the new featured account keypair
-  const signature = await sendAndConfirmTransaction(
-    connection, 
-    transaction, 
-    [payer, featuredAccount],
-    {
-      skipPreflight: true, // Skip the preflight check to debug issues
-    }
+  console.log('Transaction signature:', signature);
+  console.log('Vote tokens released successfully!');
+}
+
+// Feature a DAO, or extend its existing listing
+async function createFeatured(
+  payer,
+  daoId,
+  days = 1 // Number of days to feature the DAO (default: 1 day)
+) {
+  const config = await fetchConfig();
+  const dao = new PublicKey(daoId);
+  const featuredAccount = findFeaturedAddress(dao);
+  console.log(`Featuring DAO for ${days} days, base rate: $${config.fees.featuredPerDay.toNumber() / 100} per day`);
+  
+  // A DAO has a single featured listing, which is renewed once it exists
+  const existing = await connection.getAccountInfo(featuredAccount);
+  const instruction = existing === null ? Instruction.Featured : Instruction.RenewFeatured;
+  const data = serializeFeaturedInstruction(instruction, daoId, days);
+  
+  const transaction = new Transaction().add(
+    new TransactionInstruction({
+      keys: [
+        { pubkey: payer.publicKey, isSigner: true, isWritable: true },
+        { pubkey: featuredAccount, isSigner: false, isWritable: true },
+        { pubkey: dao, isSigner: false, isWritable: false },
+        { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+        { pubkey: config.feeRecipient, isSigner: false, isWritable: true },
+        { pubkey: SOL_USD_PRICE_ACCOUNT, isSigner: false, isWritable: false },
+        { pubkey: findConfigAddress(), isSigner: false, isWritable: false },
+        // Receives the config's treasury rebate
+        { pubkey: findTreasuryAddress(dao), isSigner: false, isWritable: true },
+      ],
+      programId,
+      data,
+    })
   );
   
+  console.log('Sending transaction...');
+  const signature = await sendAndConfirmTransaction(connection, transaction, [payer]);
+  
   console.log('Transaction signature:', signature);
   console.log('DAO featured successfully!');
-  console.log('Featured ID:', featuredAccount.publicKey.toString());
+  console.log('Featured ID:', featuredAccount.toString());
   
-  return featuredAccount.publicKey;
+  return featuredAccount;
 }
 
 // Activate a module for a DAO
 async function activateModule(
   payer,
   daoId,
-  moduleType, // "POD" or "POL"
-  sol_price_usd = null // Optional parameter for direct price input
+  moduleType // "POD" or "POL"
 ) {
   // Validate module type
-  if (moduleType !== "POD" && moduleType !== "POL") {
+  if (!(moduleType in ModuleType)) {
     throw new Error('Invalid module type. Must be either "POD" or "POL".');
   }
   
-  // Get current SOL price if not provided
-  if (sol_price_usd === null) {
-    sol_price_usd = await getSolPrice();
-  }
-  console.log(`Activating module ${moduleType} with SOL price: $${sol_price_usd/100} (${sol_price_usd} cents)`);
-  
-  // Calculate expected fee based on SOL price
-  const expectedFeeInSol = 20 / (sol_price_usd / 100);
-  const expectedFeeInLamports = Math.round(expectedFeeInSol * 1_000_000_000);
-  console.log(`Expected fee: ${expectedFeeInSol} SOL (${expectedFeeInLamports} lamports)`);
-  
-  // Generate a new keypair for the Module account
-  const moduleAccount = Keypair.generate();
-  console.log('Generated Module account:', moduleAccount.publicKey.toString());
+  const config = await fetchConfig();
+  const dao = new PublicKey(daoId);
+  const moduleAccount = findModuleAddress(dao);
+  const fee = moduleType === 'POD' ? config.fees.podModule : config.fees.polModule;
+  console.log(`Activating module ${moduleType}, fee: $${fee.toNumber() / 100} in SOL`);
   
   // Serialize instruction data
-  const data = serializeModulesInstruction(
-    daoId,
-    moduleType,
-    sol_price_usd
-  );
+  const data = serializeModulesInstruction(daoId, ModuleType[moduleType]);
   
-  // Create instruction
-  const instruction = new TransactionInstruction({
-    keys: [
-      { pubkey: payer.publicKey, isSigner: true, isWritable: true },
-      { pubkey: moduleAccount.publicKey, isSigner: true, isWritable: true },
-      { pubkey: new PublicKey(daoId), isSigner: false, isWritable: false },
-      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
-      { pubkey: FEE_ADDRESS, isSigner: false, isWritable: true },
-    ],
-    programId,
-    data,
-  });
-  
-  // Create transaction
-  const transaction = new Transaction().add(instruction);
+  const transaction = new Transaction().add(
+    new TransactionInstruction({
+      keys: [
+        { pubkey: payer.publicKey, isSigner: true, isWritable: true },
+        { pubkey: moduleAccount, isSigner: false, isWritable: true },
+        { pubkey: dao, isSigner: false, isWritable: false },
+        { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+        { pubkey: config.feeRecipient, isSigner: false, isWritable: true },
+        { pubkey: SOL_USD_PRICE_ACCOUNT, isSigner: false, isWritable: false },
+        { pubkey: findConfigAddress(), isSigner: false, isWritable: false },
+        // Receives the config's treasury rebate
+        { pubkey: findTreasuryAddress(dao), isSigner: false, isWritable: true },
+      ],
+      programId,
+      data,
+    })
+  );
   
   console.log('Sending transaction...');
-  // Sign transaction with both payer and the new module account keypair
-  const signature = await sendAndConfirmTransaction(
-    connection, 
-    transaction, 
-    [payer, moduleAccount],
-    {
-      skipPreflight: true, // Skip the preflight check to debug issues
-    }
-  );
+  const signature = await sendAndConfirmTransaction(connection, transaction, [payer]);
   
   console.log('Transaction signature:', signature);
   console.log(`Module ${moduleType} activated successfully!`);
-  console.log('Module ID:', moduleAccount.publicKey.toString());
+  console.log('Module registry ID:', moduleAccount.toString());
   
-  return moduleAccount.publicKey;
+  return moduleAccount;
+}
+
+// Add a member to a pod, or remove one, as the pod authority
+async function updatePodMember(payer, instruction, daoId, podName, member) {
+  const dao = new PublicKey(daoId);
+  const podAccount = findPodAddress(dao, podName);
+  
+  const transaction = new Transaction().add(
+    new TransactionInstruction({
+      keys: [
+        { pubkey: payer.publicKey, isSigner: true, isWritable: false },
+        { pubkey: podAccount, isSigner: false, isWritable: true },
+        // Pod members can only change while the POD module is enabled
+        { pubkey: findModuleAddress(dao), isSigner: false, isWritable: false },
+      ],
+      programId,
+      data: Buffer.concat([serializeU8(instruction), new PublicKey(member).toBuffer()]),
+    })
+  );
+  
+  const signature = await sendAndConfirmTransaction(connection, transaction, [payer]);
+  console.log('Transaction signature:', signature);
+  return podAccount;
+}
+
+async function addPodMember(payer, daoId, podName, member) {
+  const podAccount = await updatePodMember(payer, Instruction.AddPodMember, daoId, podName, member);
+  console.log(`Added ${member} to pod ${podAccount.toString()}`);
+}
+
+async function removePodMember(payer, daoId, podName, member) {
+  const podAccount =
+    await updatePodMember(payer, Instruction.RemovePodMember, daoId, podName, member);
+  console.log(`Removed ${member} from pod ${podAccount.toString()}`);
 }
 
 // Example usage
@@ -595,7 +768,7 @@ async function main() {
     const balance = await connection.getBalance(payer.publicKey);
     console.log('Wallet balance:', balance / 1_000_000_000, 'SOL');
     
-    // 1. Create a DAO
+    // 1. Create a DAO, one vote per wallet
     const daoId = await createDao(
       payer,
       'My DAO',
@@ -606,11 +779,10 @@ async function main() {
       'https://instagram.com/mydao',
       'https://tiktok.com/@mydao',
       'https://mydao.org',
-      'treasury_account_pubkey', // This should be a valid PublicKey string
       'profile_url'
     );
     
-    // 2. Create a proposal
+    // 2. Create a yes/no proposal
     const now = Math.floor(Date.now() / 1000);
     const oneWeekFromNow = now + (7 * 24 * 60 * 60);
     
@@ -624,11 +796,12 @@ async function main() {
       oneWeekFromNow // End time (one week from now)
     );
     
-    // 3. Vote on the proposal
+    // 3. Vote "For" on the proposal
     await vote(
       payer,
-      'yes', // Can be 'yes', 'no', or 'abstain'
-      proposalId.toString()
+      daoId.toString(),
+      proposalId.toString(),
+      VoteChoice.option(0) // VoteChoice.option(1) for "Against", or VoteChoice.abstain()
     );
     
     // 4. Create a featured listing for the DAO (7 days)
@@ -642,9 +815,9 @@ async function main() {
     await activateModule(
       payer,
       daoId.toString(),
-      "POD"
+      'POD'
     );
-    
+  
   } catch (error) {
     console.error('Error:', error);
   }
@@ -658,8 +831,25 @@ module.exports = {
   createDao,
   createProposal,
   vote,
+  finalizeProposal,
+  releaseVoteTokens,
   createFeatured,
   activateModule,
-  getSolPrice,
+  addPodMember,
+  removePodMember,
+  fetchConfig,
+  fetchDao,
+  findDaoAddress,
+  findProposalAddress,
+  findVoteAddress,
+  findFeaturedAddress,
+  findModuleAddress,
+  findPodAddress,
+  findTreasuryAddress,
+  findConfigAddress,
+  findReputationAddress,
+  VotingMode,
+  VoteChoice,
+  ModuleType,
   connection,
-}; 
\ No newline at end of file
+};
@@ -1,13 +1,7 @@
 const fs = require('fs');
 const path = require('path');
 const { Keypair } = require('@solana/web3.js');
-const { createDao, createProposal, vote, connection } = require('./client.js');
-
-// Mock SOL price function since node-fetch is having issues
-const mockGetSolPrice = async () => {
-  // Let's assume SOL is $100 USD
-  return 10000; // $100.00 in cents
-};
+const { createDao, createProposal, vote, fetchConfig, VoteChoice, connection } = require('./client.js');
 
 // Helper function to load keypair from file
 function loadKeypairFromFile(filePath) {
@@ -33,15 +27,11 @@ async function main() {
       return;
     }
     
-    // 1. Use mock SOL price ($100 USD)
-    const solPriceUsd = 10000; // $100.00 in cents
-    console.log(`Using mock SOL price: $${solPriceUsd/100} (${solPriceUsd} cents)`);
-    
-    // Calculate expected fee
-    const expectedFee = 20 / (solPriceUsd / 100); // $20 / SOL price
-    console.log(`Expected fee: ~${expectedFee.toFixed(4)} SOL`);
+    // 1. Read the fees from the program config, priced in SOL on-chain from the Pyth feed
+    const config = await fetchConfig();
+    console.log(`DAO creation fee: $${config.fees.createDao.toNumber() / 100}`);
     
-    // 2. Create a DAO with the mock SOL price
+    // 2. Create a DAO, one vote per wallet
     console.log('Creating DAO...');
     const timestamp = Date.now();
     const daoId = await createDao(
@@ -54,10 +44,9 @@ async function main() {
       'https://instagram.com/testdao',
       'https://tiktok.com/@testdao',
       'https://testdao.org',
-      'treasury_account_pubkey',
       'profile_url',
-      'token_address',
-      solPriceUsd // Pass the mock SOL price directly
+      '', // No governance token
+      { quorum: 1, approvalThresholdBps: 5_000 }
     );
     
     console.log('DAO created successfully with ID:', daoId.toString());
@@ -69,9 +58,9 @@ async function main() {
     
     // 3. Create a proposal
     console.log('Creating proposal...');
-    // Use current timestamp + 60 seconds for start time to ensure it's in the future
+    // Use current timestamp + 20 seconds for start time to ensure it's in the future
     const now = Math.floor(Date.now() / 1000);
-    const startTime = now + 60; // Start 1 minute in the future
+    const startTime = now + 20; // Start 20 seconds in the future
     const endTime = now + (7 * 24 * 60 * 60); // End one week later
     
     const proposalId = await createProposal(
@@ -80,18 +69,24 @@ async function main() {
       'A test proposal for our DAO',
       daoId.toString(),
       '', // Empty pod_id
-      startTime, // Start time (now + 60 seconds)
+      startTime, // Start time (now + 20 seconds)
       endTime // End time (one week from now)
     );
     
     console.log('Proposal created successfully with ID:', proposalId.toString());
     
-    // 4. Vote on the proposal
+    // 4. Vote on the proposal, once voting has started
+    const waitSeconds = startTime - Math.floor(Date.now() / 1000) + 5;
+    if (waitSeconds > 0) {
+      console.log(`Waiting ${waitSeconds} seconds for voting to start...`);
+      await new Promise((resolve) => setTimeout(resolve, waitSeconds * 1000));
+    }
     console.log('Voting on proposal...');
     const voteId = await vote(
       payer,
-      'for', // Can be "for" or "against"
-      proposalId.toString()
+      daoId.toString(),
+      proposalId.toString(),
+      VoteChoice.option(0) // "For", VoteChoice.option(1) for "Against"
     );
     
     console.log('Vote recorded successfully with ID:', voteId.toString());
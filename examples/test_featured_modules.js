@@ -1,7 +1,7 @@
 const fs = require('fs');
 const path = require('path');
 const { Keypair } = require('@solana/web3.js');
-const { createDao, activateModule, createFeatured, connection } = require('./client.js');
+const { createDao, activateModule, createFeatured, fetchConfig, connection } = require('./client.js');
 
 // Helper function to load keypair from file
 function loadKeypairFromFile(filePath) {
@@ -27,21 +27,13 @@ async function main() {
       return;
     }
     
-    // 1. Use mock SOL price ($100 USD)
-    const solPriceUsd = 10000; // $100.00 in cents
-    console.log(`Using mock SOL price: $${solPriceUsd/100} (${solPriceUsd} cents)`);
+    // 1. Read the fees from the program config, priced in SOL on-chain from the Pyth feed
+    const config = await fetchConfig();
+    console.log(`DAO creation fee: $${config.fees.createDao.toNumber() / 100}`);
+    console.log(`POD module fee: $${config.fees.podModule.toNumber() / 100}`);
+    console.log(`Featured base rate: $${config.fees.featuredPerDay.toNumber() / 100} per day`);
     
-    // Calculate expected fee for standard operations
-    const expectedFee = 20 / (solPriceUsd / 100); // $20 / SOL price
-    console.log(`Expected fee: ~${expectedFee.toFixed(4)} SOL per operation`);
-    
-    // Calculate expected fees for featured listings
-    const oneDayFee = (20 * 1) / (solPriceUsd / 100); // $20 * 1 day / SOL price
-    const threeDayFee = (20 * 3) / (solPriceUsd / 100); // $20 * 3 days / SOL price
-    console.log(`Expected featured fee for 1 day: ~${oneDayFee.toFixed(4)} SOL`);
-    console.log(`Expected featured fee for 3 days: ~${threeDayFee.toFixed(4)} SOL`);
-    
-    // 2. Create a DAO with the mock SOL price
+    // 2. Create a DAO
     console.log('------------------------------------');
     console.log('Step 1: Creating DAO...');
     const timestamp = Date.now();
@@ -55,10 +47,7 @@ async function main() {
       'https://instagram.com/testdao',
       'https://tiktok.com/@testdao',
       'https://testdao.org',
-      'treasury_account_pubkey',
-      'profile_url',
-      'token_address',
-      solPriceUsd // Pass the mock SOL price directly
+      'profile_url'
     );
     
     console.log('DAO created successfully with ID:', daoId.toString());
@@ -74,8 +63,7 @@ async function main() {
     const moduleId = await activateModule(
       payer,
       daoId.toString(),
-      "POD", // Activate POD module
-      solPriceUsd // Pass the mock SOL price directly
+      "POD" // Activate POD module
     );
     
     console.log('Module activated successfully with ID:', moduleId.toString());
@@ -91,8 +79,7 @@ async function main() {
     const featuredId1Day = await createFeatured(
       payer,
       daoId.toString(),
-      1, // Feature for 1 day
-      solPriceUsd // Pass the mock SOL price directly
+      1 // Feature for 1 day
     );
     
     console.log('Featured status (1 day) enabled successfully with ID:', featuredId1Day.toString());
@@ -102,17 +89,16 @@ async function main() {
     console.log('Wallet balance after 1-day featured:', balanceAfter1DayFeatured / 1_000_000_000, 'SOL');
     console.log('1-day featured fee paid:', (balanceAfterModule - balanceAfter1DayFeatured) / 1_000_000_000, 'SOL');
     
-    // 5. Enable featured status for the DAO (for 3 days)
+    // 5. Extend the featured status of the DAO (by 3 days)
     console.log('------------------------------------');
-    console.log('Step 3b: Enabling featured status for 3 days...');
+    console.log('Step 3b: Extending featured status by 3 days...');
     const featuredId3Days = await createFeatured(
       payer,
       daoId.toString(),
-      3, // Feature for 3 days
-      solPriceUsd // Pass the mock SOL price directly
+      3 // Renews the existing listing for 3 more days
     );
     
-    console.log('Featured status (3 days) enabled successfully with ID:', featuredId3Days.toString());
+    console.log('Featured status extended by 3 days with ID:', featuredId3Days.toString());
     
     // Check wallet balance after enabling 3-day featured status
     const balanceAfter3DayFeatured = await connection.getBalance(payer.publicKey);
@@ -123,32 +109,19 @@ async function main() {
     console.log('Test completed successfully!');
     console.log('Summary:');
     console.log('- DAO ID:', daoId.toString());
-    console.log('- Module ID:', moduleId.toString());
-    console.log('- Featured ID (1 day):', featuredId1Day.toString());
-    console.log('- Featured ID (3 days):', featuredId3Days.toString());
+    console.log('- Module registry ID:', moduleId.toString());
+    console.log('- Featured ID:', featuredId1Day.toString());
     console.log('- Total SOL spent:', (balance - balanceAfter3DayFeatured) / 1_000_000_000, 'SOL');
     
-    // Verify fee calculations
-    const actual1DayFee = (balanceAfterModule - balanceAfter1DayFeatured) / 1_000_000_000;
-    const actual3DayFee = (balanceAfter1DayFeatured - balanceAfter3DayFeatured) / 1_000_000_000;
-    
+    // A DAO has a single featured listing, which the second payment extends
+    const sameListing = featuredId1Day.equals(featuredId3Days);
     console.log('------------------------------------');
-    console.log('FEE VERIFICATION:');
-    console.log(`1-day featured - Expected: ${oneDayFee.toFixed(4)} SOL, Actual: ${actual1DayFee.toFixed(4)} SOL`);
-    console.log(`3-day featured - Expected: ${threeDayFee.toFixed(4)} SOL, Actual: ${actual3DayFee.toFixed(4)} SOL`);
-    
-    // Check if fees are approximately correct (within 0.01 SOL tolerance for rent)
-    const tolerance = 0.01;
-    const fee1DayMatch = Math.abs(actual1DayFee - oneDayFee) < tolerance;
-    const fee3DayMatch = Math.abs(actual3DayFee - threeDayFee) < tolerance;
-    
-    console.log(`1-day fee match: ${fee1DayMatch ? '✅ PASS' : '❌ FAIL'}`);
-    console.log(`3-day fee match: ${fee3DayMatch ? '✅ PASS' : '❌ FAIL'}`);
+    console.log(`Listing renewed in place: ${sameListing ? '✅ PASS' : '❌ FAIL'}`);
     
-    if (fee1DayMatch && fee3DayMatch) {
+    if (sameListing) {
       console.log('🎉 Featured days tests PASSED!');
     } else {
-      console.log('⚠️  Some featured tests failed - check fee calculations');
+      console.log('⚠️  The renewal created a new featured listing');
     }
     
   } catch (error) {
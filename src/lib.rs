@@ -1,25 +1,58 @@
 use borsh::{BorshDeserialize, BorshSerialize};
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
+    bpf_loader_upgradeable::{self, UpgradeableLoaderState},
     entrypoint,
     entrypoint::ProgramResult,
     msg,
     program_error::ProgramError,
     pubkey::Pubkey,
     program::{invoke, invoke_signed},
+    program_pack::Pack,
     system_instruction,
     sysvar::{rent::Rent, Sysvar},
     clock::Clock,
+    instruction::{AccountMeta, Instruction},
 };
+use spl_associated_token_account::{
+    get_associated_token_address,
+    instruction::create_associated_token_account_idempotent,
+};
+use spl_token::state::{Account as TokenAccount, Mint};
 use std::str::FromStr;
 
+pub mod oracle;
+pub mod pda;
+
 // Program entrypoint
 entrypoint!(process_instruction);
 
-// Fee address where funds will be sent
-const FEE_ADDRESS: &str = "BAGek78CDYQ8phuDqNk7sQzD7LdJeKkb7jD4y2AyR3tJ";
-// Fee in USD for creating a DAO
-const CREATE_DAO_FEE_USD: u64 = 20; // $20 USD
+// Maximum number of instructions a proposal can carry
+pub const MAX_PROPOSAL_INSTRUCTIONS: usize = 10;
+// Maximum number of options of a multiple choice proposal
+pub const MAX_PROPOSAL_OPTIONS: usize = 8;
+// Maximum length of a proposal option label, in bytes
+pub const MAX_OPTION_LABEL_LEN: usize = 32;
+// Maximum number of stablecoin mints accepted for fees
+pub const MAX_STABLE_MINTS: usize = 5;
+// Length of a featured day, in seconds
+pub const SECONDS_PER_DAY: i64 = 86_400;
+// Maximum number of featured slots auctioned per period
+pub const MAX_FEATURED_SLOTS: u8 = 10;
+// Maximum number of featured pricing tiers
+pub const MAX_FEATURED_TIERS: usize = 8;
+// Maximum number of members in a pod
+pub const MAX_POD_MEMBERS: usize = 32;
+// Reputation points granted by one endorsement
+pub const ENDORSEMENT_POINTS: u64 = 100;
+// Minimum time between two endorsements from the same member, in seconds
+pub const ENDORSEMENT_COOLDOWN: i64 = SECONDS_PER_DAY;
+// Time for a reputation score to halve, in seconds
+pub const REPUTATION_HALF_LIFE: i64 = 30 * SECONDS_PER_DAY;
+// Reputation a member needs to endorse others, the DAO authority needs none
+pub const MIN_ENDORSER_REPUTATION: u64 = ENDORSEMENT_POINTS;
+// Minimum time between two endorsements of the same member by the same endorser, in seconds
+pub const ENDORSEMENT_REPEAT_COOLDOWN: i64 = REPUTATION_HALF_LIFE;
 
 // Error codes specific to this program
 #[derive(Debug, thiserror::Error)]
@@ -40,6 +73,110 @@ pub enum DaoError {
     InsufficientFunds,
     #[error("Invalid SOL Price")]
     InvalidSolPrice,
+    #[error("Invalid Account Address")]
+    InvalidAccountAddress,
+    #[error("Already Voted")]
+    AlreadyVoted,
+    #[error("Invalid Account Owner")]
+    InvalidAccountOwner,
+    #[error("Proposal Mismatch")]
+    ProposalMismatch,
+    #[error("Voting Not Started")]
+    VotingNotStarted,
+    #[error("Voting Ended")]
+    VotingEnded,
+    #[error("Invalid Account Type")]
+    InvalidAccountType,
+    #[error("DAO Mismatch")]
+    DaoMismatch,
+    #[error("Invalid Token Address")]
+    InvalidTokenAddress,
+    #[error("Invalid Token Account")]
+    InvalidTokenAccount,
+    #[error("No Voting Power")]
+    NoVotingPower,
+    #[error("Invalid Governance Config")]
+    InvalidGovernanceConfig,
+    #[error("Voting Not Ended")]
+    VotingNotEnded,
+    #[error("Proposal Already Finalized")]
+    ProposalAlreadyFinalized,
+    #[error("Proposal Not Passed")]
+    ProposalNotPassed,
+    #[error("Invalid Withdrawal")]
+    InvalidWithdrawal,
+    #[error("Invalid Proposal Instruction")]
+    InvalidProposalInstruction,
+    #[error("Unauthorized")]
+    Unauthorized,
+    #[error("Proposal Not Queued")]
+    ProposalNotQueued,
+    #[error("Timelock Not Expired")]
+    TimelockNotExpired,
+    #[error("Timelock Expired")]
+    TimelockExpired,
+    #[error("Invalid Price Account")]
+    InvalidPriceAccount,
+    #[error("Stale Price")]
+    StalePrice,
+    #[error("Price Confidence Too Wide")]
+    PriceConfidenceTooWide,
+    #[error("Invalid Module Type")]
+    InvalidModuleType,
+    #[error("Invalid Config")]
+    InvalidConfig,
+    #[error("Invalid Fee Mint")]
+    InvalidFeeMint,
+    #[error("Invalid Referrer")]
+    InvalidReferrer,
+    #[error("Featured Not Expired")]
+    FeaturedNotExpired,
+    #[error("Featured Slots Auctioned")]
+    FeaturedSlotsAuctioned,
+    #[error("Auction Closed")]
+    AuctionClosed,
+    #[error("Auction Not Ended")]
+    AuctionNotEnded,
+    #[error("Auction Not Settled")]
+    AuctionNotSettled,
+    #[error("Bid Too Low")]
+    BidTooLow,
+    #[error("Bid Not Refundable")]
+    BidNotRefundable,
+    #[error("Bid Not Winning")]
+    BidNotWinning,
+    #[error("Module Already Enabled")]
+    ModuleAlreadyEnabled,
+    #[error("Module Not Enabled")]
+    ModuleNotEnabled,
+    #[error("Pod Full")]
+    PodFull,
+    #[error("Already Pod Member")]
+    AlreadyPodMember,
+    #[error("Not Pod Member")]
+    NotPodMember,
+    #[error("Invalid Endorsement")]
+    InvalidEndorsement,
+    #[error("Endorsement Rate Limited")]
+    EndorsementRateLimited,
+    #[error("Invalid Proposal Options")]
+    InvalidProposalOptions,
+    #[error("Proposal Not Tallying")]
+    ProposalNotTallying,
+    #[error("Voice Credits Exceeded")]
+    VoiceCreditsExceeded,
+    #[error("Featured Schedule Conflict")]
+    FeaturedScheduleConflict,
+    #[error("Auction Bids Outstanding")]
+    AuctionBidsOutstanding,
+    #[error("Insufficient Reputation")]
+    InsufficientReputation,
+    #[error("Proposal Not Finalized")]
+    ProposalNotFinalized,
+    #[error("No Locked Tokens")]
+    NoLockedTokens,
+    #[error("Featured Period Ended")]
+    FeaturedPeriodEnded,
 }
 
 impl From<DaoError> for ProgramError {
@@ -55,9 +192,16 @@ pub enum DaoInstruction {
     /// 
     /// Accounts:
     /// 0. `[signer]` Creator account
-    /// 1. `[writable]` New DAO account
+    /// 1. `[writable]` New DAO account (PDA: `["dao", creator, hash(name)]`)
     /// 2. `[]` System program
-    /// 3. `[writable]` Fee recipient account
+    /// 3. `[writable]` Fee recipient account, or its associated token account for `fee_mint`
+    /// 4. `[writable]` DAO treasury account (PDA: `["treasury", dao]`)
+    /// 5. `[]` Pyth SOL/USD price account, or the creator's token account for `fee_mint`
+    /// 6. `[]` Config account (PDA: `["config"]`)
+    /// 7. `[]` Fee mint (only when paying with `fee_mint`)
+    /// 8. `[]` Token program (only when paying with `fee_mint`)
+    /// 9. `[writable]` Referrer account, or its associated token account for `fee_mint`
+    ///    (only with a `referrer`)
     CreateDao {
         name: String,
         description: String,
@@ -67,74 +211,480 @@ pub enum DaoInstruction {
         instagram: String,
         tiktok: String,
         website: String,
-        treasury: String,
         profile: String,
-        token_address: String,
-        sol_price_usd: u64, // Current SOL price in USD cents (e.g., 10000 = $100.00)
+        token_address: String, // Governance token mint, or empty for one vote per wallet
+        governance: GovernanceConfig,
+        fee_mint: Option<Pubkey>, // Allowlisted stablecoin to pay the fee with, instead of SOL
+        referrer: Option<Pubkey>, // Wallet receiving the config's referral share of the fee
     },
     
     /// Create a new proposal
     /// 
     /// Accounts:
     /// 0. `[signer]` Creator account
-    /// 1. `[writable]` New proposal account
+    /// 1. `[writable]` New proposal account (PDA: `["proposal", dao, creator, hash(name)]`)
     /// 2. `[]` DAO account
     /// 3. `[]` System program
-    /// 4. `[writable]` Fee recipient account (optional)
+    /// 4. `[]` Config account (PDA: `["config"]`)
+    /// 5. `[]` Pod account (only when `pod_id` is not empty)
+    /// 6. `[]` Module registry account (PDA: `["module", dao]`, only when `pod_id` is not empty)
+    /// 7. `[writable]` Fee recipient account (only when the config charges a proposal fee)
+    /// 8. `[]` Pyth SOL/USD price account (only when the config charges a proposal fee)
+    ///
+    /// Proposals made for a pod must be created by one of its members, while the DAO's POD
+    /// module is enabled. Proposals with
+    /// `options` are multiple choice, or ranked choice with `ranked`, and can't carry a
+    /// withdrawal or instructions.
     CreateProposal {
         name: String,
         description: String,
-        dao_id: String,
-        pod_id: String, // Can be empty
+        dao_id: Pubkey, // Must match the DAO account
+        pod_id: String, // Address of a pod of the DAO, or empty
         start_time: i64,
         end_time: i64,
+        withdrawal: Option<TreasuryWithdrawal>, // Paid out of the treasury once executed
+        instructions: Vec<ProposalInstruction>, // Invoked with the treasury as signer once executed
+        options: Vec<String>, // Labels of a multiple choice proposal, or empty for "For"/"Against"
+        ranked: bool, // Voters rank the options, decided by instant runoff
     },
     
     /// Vote on a proposal
     /// 
     /// Accounts:
     /// 0. `[signer]` Voter account
-    /// 1. `[writable]` New vote account
-    /// 2. `[]` Proposal account
+    /// 1. `[writable]` New vote account (PDA: `["vote", proposal, voter]`)
+    /// 2. `[writable]` Proposal account
     /// 3. `[]` System program
-    /// 4. `[writable]` Fee recipient account (optional)
+    /// 4. `[]` DAO account the proposal belongs to
+    /// 5. `[]` Config account (PDA: `["config"]`)
+    /// 6. `[writable]` Voter's associated token account for the DAO's governance mint
+    ///    (only when the DAO has a `token_address`, in `Token` or `Quadratic` voting mode)
+    /// 7. `[writable]` Vote account's associated token account, escrowing the voter's tokens
+    /// 8. `[]` Governance mint
+    /// 9. `[]` Token program
+    /// 10. `[]` Associated token program
+    /// 
+    /// In `Reputation` voting mode, instead of the token accounts:
+    /// 6. `[]` Module registry account (PDA: `["module", dao]`)
+    /// 7. `[]` Voter's reputation account (PDA: `["reputation", dao, voter]`)
+    /// 
+    /// Followed by, only when the config charges a vote fee:
+    /// - `[writable]` Fee recipient account
+    /// - `[]` Pyth SOL/USD price account
+    ///
+    /// Each voter can only vote once per proposal, for one of its options or to abstain.
+    /// Abstentions count toward the quorum only. Votes are weighted by the
+    /// voter's governance token balance, or count as one vote per wallet. DAOs in
+    /// `Reputation` voting mode weigh votes by the voter's POL reputation score instead.
+    /// 
+    /// The voter's whole governance token balance is escrowed in the vote account's
+    /// associated token account, so the same tokens can't vote again from another wallet.
+    /// They can be taken back with `ReleaseVoteTokens` once the proposal is finalized.
+    /// 
+    /// In `Quadratic` voting mode, every voter (every token holder when the DAO has a
+    /// governance token) spreads the DAO's `voice_credits` budget across the options with a
+    /// `Credits` ballot, each option counting the square root of the credits spent on it.
+    /// Yes/no ballots spend on a single side. Abstentions weigh as much as the whole budget
+    /// spent on one option.
     Vote {
-        vote: String,
+        choice: VoteChoice,
         proposal_id: String,
     },
 
-    /// Feature a DAO (paid advertisement)
+    /// Feature a DAO (paid advertisement), starting now
     /// 
     /// Accounts:
     /// 0. `[signer]` Creator account
-    /// 1. `[writable]` New featured account
+    /// 1. `[writable]` New featured account (PDA: `["featured", dao]`)
     /// 2. `[]` DAO account
     /// 3. `[]` System program
-    /// 4. `[writable]` Fee recipient account
+    /// 4. `[writable]` Fee recipient account, or its associated token account for `fee_mint`
+    /// 5. `[]` Pyth SOL/USD price account, or the creator's token account for `fee_mint`
+    /// 6. `[]` Config account (PDA: `["config"]`)
+    /// 7. `[]` Fee mint (only when paying with `fee_mint`)
+    /// 8. `[]` Token program (only when paying with `fee_mint`)
+    /// 9. `[writable]` DAO treasury account, or its associated token account for `fee_mint`,
+    ///    receiving the config's treasury rebate
+    /// 10. `[]` DAO treasury account (only when paying with `fee_mint`)
+    /// 11. `[]` Associated token program (only when paying with `fee_mint`)
+    /// 
+    /// With a stablecoin, the treasury's associated token account is created if missing.
+    /// A DAO can only have one featured listing at a time. Use `RenewFeatured` to extend it.
+    /// Only the DAO authority can pay to feature the DAO, unless the DAO allows sponsors.
     Featured {
-        dao_id: String,
+        dao_id: Pubkey, // Must match the DAO account
         days: u64, // Number of days to feature the DAO
-        sol_price_usd: u64, // Current SOL price in USD cents
+        fee_mint: Option<Pubkey>, // Allowlisted stablecoin to pay the fee with, instead of SOL
     },
     
     /// Enable DAO modules
     /// 
     /// Accounts:
-    /// 0. `[signer]` Creator account
-    /// 1. `[writable]` New module account
+    /// 0. `[signer]` DAO authority or module manager
+    /// 1. `[writable]` Module registry account (PDA: `["module", dao]`), created on first use
     /// 2. `[]` DAO account
     /// 3. `[]` System program
-    /// 4. `[writable]` Fee recipient account
+    /// 4. `[writable]` Fee recipient account, or its associated token account for `fee_mint`
+    /// 5. `[]` Pyth SOL/USD price account, or the creator's token account for `fee_mint`
+    /// 6. `[]` Config account (PDA: `["config"]`)
+    /// 7. `[]` Fee mint (only when paying with `fee_mint`)
+    /// 8. `[]` Token program (only when paying with `fee_mint`)
+    /// 9. `[writable]` DAO treasury account, or its associated token account for `fee_mint`,
+    ///    receiving the config's treasury rebate
+    /// 10. `[]` DAO treasury account (only when paying with `fee_mint`)
+    /// 11. `[]` Associated token program (only when paying with `fee_mint`)
+    /// 
+    /// With a stablecoin, the treasury's associated token account is created if missing.
+    /// Only the DAO authority and its module manager can enable modules.
+    /// Fails with `ModuleAlreadyEnabled` if the DAO already has the module enabled.
     Modules {
-        dao_id: String,
-        module_type: String, // "POD" or "POL"
-        sol_price_usd: u64, // Current SOL price in USD cents
+        dao_id: Pubkey, // Must match the DAO account
+        module_type: ModuleType,
+        fee_mint: Option<Pubkey>, // Allowlisted stablecoin to pay the fee with, instead of SOL
+    },
+    
+    /// Finalize a proposal once its voting period is over
+    /// 
+    /// Accounts:
+    /// 0. `[writable]` Proposal account
+    /// 1. `[]` DAO account the proposal belongs to
+    ///
+    /// Can be called by anyone after `end_time`. When the DAO's quorum is
+    /// reached and the approval threshold is met, the proposal is queued for
+    /// execution after the DAO's execution delay.
+    /// 
+    /// Ranked choice proposals reaching the quorum are decided by instant runoff
+    /// instead: when no option has a majority of the first preferences, the
+    /// proposal moves to `Tallying` and the later rounds are counted with
+    /// `CountRankedVotes`.
+    FinalizeProposal,
+    
+    /// Deposit lamports into a DAO treasury
+    /// 
+    /// Accounts:
+    /// 0. `[signer, writable]` Depositor account
+    /// 1. `[]` DAO account
+    /// 2. `[writable]` DAO treasury account
+    /// 3. `[]` System program
+    Deposit {
+        amount: u64, // Amount in lamports
+    },
+    
+    /// Deposit SPL tokens into a DAO treasury
+    /// 
+    /// Accounts:
+    /// 0. `[signer, writable]` Depositor account
+    /// 1. `[writable]` Depositor token account
+    /// 2. `[]` DAO account
+    /// 3. `[]` DAO treasury account
+    /// 4. `[writable]` Treasury associated token account (created if missing)
+    /// 5. `[]` Token mint
+    /// 6. `[]` System program
+    /// 7. `[]` Token program
+    /// 8. `[]` Associated token account program
+    DepositToken {
+        amount: u64, // Amount in the mint's base units
+    },
+    
+    /// Execute a passed proposal: pay out its treasury withdrawal, if any, then
+    /// invoke each of its instructions with the DAO treasury as signer
+    /// 
+    /// Accounts:
+    /// 0. `[signer, writable]` Executor account (pays for the recipient token account)
+    /// 1. `[writable]` Proposal account
+    /// 2. `[]` DAO account the proposal belongs to
+    /// 3. `[writable]` DAO treasury account
+    /// 4. `[writable]` Withdrawal recipient account (any account without a withdrawal)
+    /// 5. `[]` System program
+    /// 
+    /// For SPL token withdrawals:
+    /// 6. `[writable]` Treasury associated token account
+    /// 7. `[writable]` Recipient associated token account (created if missing)
+    /// 8. `[]` Token mint
+    /// 9. `[]` Token program
+    /// 10. `[]` Associated token account program
+    /// 
+    /// Followed by every program and account used by the proposal instructions,
+    /// in any order.
+    /// 
+    /// Only queued proposals whose execution delay has elapsed can be executed.
+    ExecuteProposal,
+    
+    /// Cancel a queued proposal before its execution delay elapses
+    /// 
+    /// Accounts:
+    /// 0. `[signer]` DAO authority
+    /// 1. `[writable]` Proposal account
+    /// 2. `[]` DAO account the proposal belongs to
+    CancelProposal,
+    
+    /// Create the global program config
+    /// 
+    /// Accounts:
+    /// 0. `[signer, writable]` Program upgrade authority
+    /// 1. `[writable]` New config account (PDA: `["config"]`)
+    /// 2. `[]` Program data account of the DAO program
+    /// 3. `[]` System program
+    InitializeConfig {
+        admin: Pubkey, // Key allowed to update the config
+        fee_recipient: Pubkey, // Account receiving the platform fees
+        fees: FeeSchedule,
+        stable_mints: Vec<Pubkey>, // Stablecoins accepted for fees, pegged to one USD
+        fee_split: FeeSplit,
+        featured_auction: FeaturedAuctionConfig,
+        featured_tiers: Vec<FeaturedTier>, // Discounted daily rates for longer durations
+    },
+    
+    /// Update the global program config
+    /// 
+    /// Accounts:
+    /// 0. `[signer, writable]` Config admin (pays for a larger config account)
+    /// 1. `[writable]` Config account
+    /// 2. `[]` System program
+    UpdateConfig {
+        admin: Pubkey, // New admin, or the current one to keep it
+        fee_recipient: Pubkey,
+        fees: FeeSchedule,
+        stable_mints: Vec<Pubkey>,
+        fee_split: FeeSplit,
+        featured_auction: FeaturedAuctionConfig,
+        featured_tiers: Vec<FeaturedTier>, // Discounted daily rates for longer durations
+    },
+    
+    /// Extend a DAO's featured listing, or restart it from now if it has expired
+    /// 
+    /// Accounts: same as `Featured`, with the existing featured account.
+    /// Only the DAO authority can pay to extend a listing, unless the DAO allows sponsors.
+    RenewFeatured {
+        dao_id: Pubkey, // Must match the DAO account
+        days: u64, // Number of days to add to the listing
+        fee_mint: Option<Pubkey>, // Allowlisted stablecoin to pay the fee with, instead of SOL
+    },
+    
+    /// Close an expired featured listing, returning its rent to the account that created it
+    /// 
+    /// Accounts:
+    /// 0. `[writable]` Featured account
+    /// 1. `[writable]` Featured account authority
+    /// 
+    /// Can be called by anyone once the listing has expired.
+    CloseExpiredFeatured,
+    
+    /// Bid lamports for one of the featured slots auctioned for a period
+    /// 
+    /// Accounts:
+    /// 0. `[signer, writable]` Bidder account
+    /// 1. `[writable]` Auction account (PDA: `["auction", period]`, opened by the first bid)
+    /// 2. `[writable]` Bid account (PDA: `["bid", auction, dao]`)
+    /// 3. `[]` DAO account
+    /// 4. `[]` System program
+    /// 5. `[]` Config account (PDA: `["config"]`)
+    /// 6. `[]` Pyth SOL/USD price account
+    /// 
+    /// Bidding for a period is open during the previous period. Bids are escrowed in the bid
+    /// account and bidding again adds to the DAO's bid. A bid must be worth at least the
+    /// featured fee for the period and rank among the auction's slots. Only the DAO authority
+    /// can bid, unless the DAO allows sponsors.
+    PlaceFeaturedBid {
+        dao_id: Pubkey, // Must match the DAO account
+        period: u64, // Index of the auctioned period, counted in periods since the Unix epoch
+        amount: u64, // Lamports added to the DAO's bid
+    },
+    
+    /// Settle an auction once its bidding has closed, fixing its winners
+    /// 
+    /// Accounts:
+    /// 0. `[writable]` Auction account
+    /// 
+    /// Can be called by anyone once the auctioned period has started.
+    SettleFeaturedAuction,
+    
+    /// Claim a won featured slot: pay out the escrowed bid and feature the DAO for the period
+    /// 
+    /// Accounts:
+    /// 0. `[signer, writable]` Bidder account
+    /// 1. `[writable]` Auction account
+    /// 2. `[writable]` Bid account
+    /// 3. `[writable]` Featured account (PDA: `["featured", dao]`, created if missing)
+    /// 4. `[]` DAO account
+    /// 5. `[]` System program
+    /// 6. `[]` Config account (PDA: `["config"]`)
+    /// 7. `[writable]` Fee recipient account
+    /// 8. `[writable]` DAO treasury account, receiving the config's treasury rebate
+    /// 
+    /// The period is merged into the DAO's listing when they overlap or follow each other.
+    /// Otherwise it replaces the listing once that has expired, and fails with
+    /// `FeaturedScheduleConflict` while the listing is still running. Slots can't be
+    /// claimed once the period has ended.
+    ClaimFeaturedSlot,
+    
+    /// Refund an outbid bid and close its account
+    /// 
+    /// Accounts:
+    /// 0. `[writable]` Bidder account
+    /// 1. `[writable]` Auction account
+    /// 2. `[writable]` Bid account
+    /// 
+    /// Can be called by anyone for bids that don't hold one of the auction's slots,
+    /// or whose slot was not claimed before the period ended.
+    RefundFeaturedBid,
+    
+    /// Update who besides the DAO authority may manage modules and pay for featuring
+    /// 
+    /// Accounts:
+    /// 0. `[signer, writable]` DAO authority (pays for a larger DAO account)
+    /// 1. `[writable]` DAO account
+    /// 2. `[]` System program
+    UpdateDaoPermissions {
+        module_manager: Option<Pubkey>, // Key allowed to enable and disable modules
+        allow_featured_sponsors: bool, // Let any wallet pay to feature the DAO
+    },
+    
+    /// Disable a DAO module
+    /// 
+    /// Accounts:
+    /// 0. `[signer]` DAO authority or module manager
+    /// 1. `[writable]` Module registry account (PDA: `["module", dao]`)
+    /// 2. `[]` DAO account
+    /// 
+    /// The activation fee is not refunded.
+    DisableModule {
+        dao_id: Pubkey, // Must match the DAO account
+        module_type: ModuleType,
+    },
+    
+    /// Create a pod (team) within a DAO
+    /// 
+    /// Accounts:
+    /// 0. `[signer, writable]` DAO authority, becoming the pod authority
+    /// 1. `[writable]` New pod account (PDA: `["pod", dao, hash(name)]`)
+    /// 2. `[]` DAO account
+    /// 3. `[]` Module registry account (PDA: `["module", dao]`)
+    /// 4. `[]` System program
+    /// 
+    /// Requires the DAO to have the POD module enabled. The pod account is sized for
+    /// `MAX_POD_MEMBERS` members up front.
+    CreatePod {
+        dao_id: Pubkey, // Must match the DAO account
+        name: String,
+    },
+    
+    /// Add a member to a pod
+    /// 
+    /// Accounts:
+    /// 0. `[signer]` Pod authority
+    /// 1. `[writable]` Pod account
+    /// 2. `[]` Module registry account of the pod's DAO (PDA: `["module", dao]`)
+    /// 
+    /// Requires the DAO to have the POD module enabled.
+    AddPodMember {
+        member: Pubkey,
+    },
+    
+    /// Remove a member from a pod
+    /// 
+    /// Accounts:
+    /// 0. `[signer]` Pod authority
+    /// 1. `[writable]` Pod account
+    /// 2. `[]` Module registry account of the pod's DAO (PDA: `["module", dao]`)
+    /// 
+    /// Requires the DAO to have the POD module enabled.
+    RemovePodMember {
+        member: Pubkey,
+    },
+    
+    /// Endorse another member of a DAO, raising their reputation
+    /// 
+    /// Accounts:
+    /// 0. `[signer, writable]` Endorser account (pays for new reputation accounts)
+    /// 1. `[writable]` Endorser's reputation account (PDA: `["reputation", dao, endorser]`)
+    /// 2. `[writable]` Member's reputation account (PDA: `["reputation", dao, member]`)
+    /// 3. `[]` DAO account
+    /// 4. `[]` Module registry account (PDA: `["module", dao]`)
+    /// 5. `[]` System program
+    /// 6. `[writable]` Endorsement account (PDA: `["endorsement", dao, endorser, member]`)
+    /// 7. `[]` Endorser's associated token account for the DAO's governance mint
+    ///    (only when the DAO has a `token_address`)
+    /// 8. `[]` Member's associated token account for the DAO's governance mint
+    ///    (only when the DAO has a `token_address`)
+    /// 
+    /// Requires the DAO to have the POL module enabled. Besides the DAO authority, only
+    /// members with a reputation of at least `MIN_ENDORSER_REPUTATION` can endorse. Each
+    /// member can endorse once per `ENDORSEMENT_COOLDOWN`, and the same member at most once
+    /// per `ENDORSEMENT_REPEAT_COOLDOWN`. In token DAOs, both members must hold the
+    /// governance token.
+    Endorse {
+        dao_id: Pubkey, // Must match the DAO account
+        member: Pubkey, // Member being endorsed
     },
+    
+    /// Count ranked ballots for the current instant runoff round of a proposal
+    /// 
+    /// Accounts:
+    /// 0. `[writable]` Proposal account
+    /// 1. `[]` DAO account the proposal belongs to
+    /// 2. `[writable]` Vote accounts of the proposal, as many as fit in the transaction
+    /// 
+    /// Can be called by anyone while the proposal is `Tallying`. Each ballot counts for its
+    /// highest ranked option still in the race, and ballots already counted in the round are
+    /// skipped. Once every ballot is counted, an option with a majority of the continuing
+    /// ballots wins, otherwise the option with the least weight is eliminated (the last one
+    /// on ties) and the next round starts.
+    CountRankedVotes,
+    
+    /// Close a settled auction, returning its rent to the bidder who opened it
+    /// 
+    /// Accounts:
+    /// 0. `[writable]` Auction account
+    /// 1. `[writable]` Account of the bidder who opened the auction
+    /// 
+    /// Can be called by anyone once every bid of the auction is claimed or refunded.
+    CloseFeaturedAuction,
+    
+    /// Return the governance tokens escrowed by a vote to the voter
+    /// 
+    /// Accounts:
+    /// 0. `[writable]` Voter account (receives the escrow account's rent)
+    /// 1. `[writable]` Vote account (PDA: `["vote", proposal, voter]`)
+    /// 2. `[]` Proposal account
+    /// 3. `[writable]` Vote account's associated token account for the governance mint
+    /// 4. `[writable]` Voter's associated token account for the governance mint
+    /// 5. `[]` Governance mint the tokens were locked for
+    /// 6. `[]` Token program
+    /// 
+    /// Can be called by anyone once the proposal is no longer `Voting` or `Tallying`.
+    /// The escrow token account is closed.
+    ReleaseVoteTokens,
+}
+
+// Discriminator stored as the first field of every program account
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccountType {
+    Uninitialized,
+    Dao,
+    Proposal,
+    Vote,
+    Featured,
+    Module,
+    Config,
+    FeaturedAuction,
+    FeaturedBid,
+    Pod,
+    Reputation,
+    Endorsement,
+}
+
+// Implemented by every account type owned by the program
+pub trait ProgramAccount: BorshDeserialize {
+    const ACCOUNT_TYPE: AccountType;
+    
+    fn account_type(&self) -> AccountType;
 }
 
 // DAO account data structure
 #[derive(BorshSerialize, BorshDeserialize, Debug)]
 pub struct Dao {
+    pub account_type: AccountType,
     pub authority: Pubkey,
     pub name: String,
     pub description: String,
@@ -144,640 +694,6492 @@ pub struct Dao {
     pub instagram: String,
     pub tiktok: String,
     pub website: String,
-    pub treasury: String,
+    pub treasury: Pubkey,
     pub profile: String,
     pub token_address: String,
+    pub governance: GovernanceConfig,
+    pub module_manager: Option<Pubkey>, // Can enable and disable modules besides the authority
+    pub allow_featured_sponsors: bool, // Any wallet can pay to feature the DAO
+}
+
+// Rules used to decide the outcome of a DAO's proposals
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GovernanceConfig {
+    pub quorum: u64, // Minimum total vote weight for a proposal to be valid
+    pub approval_threshold_bps: u16, // Share of the option weight needed to pass, in basis points
+    pub execution_delay: i64, // Seconds between a proposal passing and its execution
+    pub voting_mode: VotingMode,
+}
+
+// How the votes of a DAO's members are weighed
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VotingMode {
+    Token, // Governance token balance, or one vote per wallet without a token
+    Reputation { max_weight: Option<u64> }, // POL reputation score, optionally capped
+    Quadratic { voice_credits: u64 }, // Per-proposal budget of voice credits of each voter
+}
+
+impl GovernanceConfig {
+    // Check the config values are within bounds
+    pub fn validate(&self) -> ProgramResult {
+        if self.approval_threshold_bps == 0 || self.approval_threshold_bps > 10_000 {
+            return Err(DaoError::InvalidGovernanceConfig.into());
+        }
+        if self.execution_delay < 0 {
+            return Err(DaoError::InvalidGovernanceConfig.into());
+        }
+        if self.voting_mode == (VotingMode::Reputation { max_weight: Some(0) })
+            || self.voting_mode == (VotingMode::Quadratic { voice_credits: 0 })
+        {
+            return Err(DaoError::InvalidGovernanceConfig.into());
+        }
+        Ok(())
+    }
+}
+
+impl Dao {
+    // Mint of the DAO's governance token, if the DAO has one
+    pub fn governance_mint(&self) -> Option<Pubkey> {
+        Pubkey::from_str(&self.token_address).ok()
+    }
+    
+    // Check the signer can enable and disable the DAO's modules
+    pub fn check_module_manager(&self, signer: &Pubkey) -> ProgramResult {
+        if *signer != self.authority && self.module_manager != Some(*signer) {
+            return Err(DaoError::Unauthorized.into());
+        }
+        Ok(())
+    }
+    
+    // Check the signer can pay to feature the DAO
+    pub fn check_featured_sponsor(&self, signer: &Pubkey) -> ProgramResult {
+        if *signer != self.authority && !self.allow_featured_sponsors {
+            return Err(DaoError::Unauthorized.into());
+        }
+        Ok(())
+    }
 }
 
 // Proposal account data structure
 #[derive(BorshSerialize, BorshDeserialize, Debug)]
 pub struct Proposal {
+    pub account_type: AccountType,
     pub authority: Pubkey,
     pub name: String,
     pub description: String,
-    pub dao_id: String,
+    pub dao_id: Pubkey,
     pub pod_id: String,
     pub start_time: i64,
     pub end_time: i64,
+    pub kind: ProposalKind,
+    pub options: Vec<ProposalOption>,
+    pub votes_abstain: u64,
+    pub weight_abstain: u64, // Counts toward the quorum, but not the approval threshold
+    pub state: ProposalState,
+    pub finalized_at: i64, // 0 until the proposal is finalized
+    pub executable_at: i64, // 0 until the proposal is queued
+    pub withdrawal: Option<TreasuryWithdrawal>,
+    pub instructions: Vec<ProposalInstruction>,
+    pub winning_option: Option<u8>, // Approved option, set when the proposal passes
+    pub runoff: Option<Runoff>, // Instant runoff count of ranked choice proposals
 }
 
-// Vote account data structure
-#[derive(BorshSerialize, BorshDeserialize, Debug)]
-pub struct Vote {
-    pub voter: Pubkey,
-    pub vote: String,
-    pub proposal_id: String,
+// Kind of decision a proposal asks for
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProposalKind {
+    YesNo, // Options are "For" and "Against", the proposal passes when "For" is approved
+    MultipleChoice, // The proposal passes when its leading option is approved
+    RankedChoice, // Voters rank the options, the proposal is decided by instant runoff
 }
 
-// Featured account data structure
-#[derive(BorshSerialize, BorshDeserialize, Debug)]
-pub struct Featured {
-    pub authority: Pubkey,
-    pub dao_id: String,
-    pub days: u64,
-}
+// The eliminated options of a runoff are tracked in a u8 bitmask
+const _: () = assert!(MAX_PROPOSAL_OPTIONS <= u8::BITS as usize);
 
-// Module account data structure
-#[derive(BorshSerialize, BorshDeserialize, Debug)]
-pub struct Module {
-    pub authority: Pubkey,
-    pub dao_id: String,
-    pub module_type: String,
+// Instant runoff count of a ranked choice proposal
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Runoff {
+    pub round: u8, // Starts at 1, counting first preferences as votes are cast
+    pub eliminated: u8, // Bitmask of the eliminated options
+    pub tallies: Vec<u64>, // Weight counted for each option in the round
+    pub counted_weight: u64, // Weight of the ballots counted in the round
+    pub exhausted_weight: u64, // Weight of the counted ballots with no option left
 }
 
-// Program entrypoint processor
-pub fn process_instruction(
-    program_id: &Pubkey,
-    accounts: &[AccountInfo],
-    instruction_data: &[u8],
-) -> ProgramResult {
-    let instruction = DaoInstruction::try_from_slice(instruction_data)
-        .map_err(|_| ProgramError::InvalidInstructionData)?;
+// Result of an instant runoff round
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RunoffResult {
+    Winner(u8),
+    NoWinner, // Every ballot is exhausted
+    NextRound,
+}
 
-    match instruction {
-        DaoInstruction::CreateDao {
-            name,
-            description,
-            discord_server,
-            twitter,
-            telegram,
-            instagram,
-            tiktok,
-            website,
-            treasury,
-            profile,
-            token_address,
-            sol_price_usd,
-        } => {
-            process_create_dao(
-                program_id,
-                accounts,
-                name,
-                description,
-                discord_server,
-                twitter,
-                telegram,
-                instagram,
-                tiktok,
-                website,
-                treasury,
-                profile,
-                token_address,
-                sol_price_usd,
-            )
-        }
-        DaoInstruction::CreateProposal {
-            name,
-            description,
-            dao_id,
-            pod_id,
-            start_time,
-            end_time,
-        } => {
-            process_create_proposal(
-                program_id,
-                accounts,
-                name,
-                description,
-                dao_id,
-                pod_id,
-                start_time,
-                end_time,
-            )
-        }
-        DaoInstruction::Vote { vote, proposal_id } => {
-            process_vote(program_id, accounts, vote, proposal_id)
+impl Runoff {
+    pub fn new(options: usize) -> Self {
+        Runoff {
+            round: 1,
+            eliminated: 0,
+            tallies: vec![0; options],
+            counted_weight: 0,
+            exhausted_weight: 0,
         }
-        DaoInstruction::Featured { dao_id, days, sol_price_usd } => {
-            process_featured(program_id, accounts, dao_id, days, sol_price_usd)
+    }
+    
+    pub fn is_eliminated(&self, index: u8) -> bool {
+        self.eliminated & (1 << index) != 0
+    }
+    
+    // Count a ballot for its highest ranked option still in the race
+    pub fn count(&mut self, ranking: &[u8], weight: u64) -> ProgramResult {
+        let tally = match ranking.iter().find(|index| !self.is_eliminated(**index)) {
+            Some(index) => self.tallies.get_mut(*index as usize).ok_or(DaoError::InvalidVote)?,
+            None => &mut self.exhausted_weight,
+        };
+        *tally = tally.checked_add(weight).ok_or(ProgramError::ArithmeticOverflow)?;
+        self.counted_weight = self
+            .counted_weight
+            .checked_add(weight)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        Ok(())
+    }
+    
+    // Resolve the round once every ballot is counted: elect the option holding a majority
+    // of the continuing ballots, or eliminate the weakest option and start the next round
+    pub fn resolve(&mut self) -> RunoffResult {
+        let continuing = self.counted_weight.saturating_sub(self.exhausted_weight);
+        let remaining: Vec<u8> = (0..self.tallies.len() as u8)
+            .filter(|index| !self.is_eliminated(*index))
+            .collect();
+        
+        // Ties go to the first option when electing, and to the last one when eliminating
+        let tally = |index: &&u8| self.tallies[**index as usize];
+        let (Some(leader), Some(weakest)) = (
+            remaining.iter().rev().max_by_key(tally),
+            remaining.iter().rev().min_by_key(tally),
+        ) else {
+            return RunoffResult::NoWinner;
+        };
+        if continuing == 0 {
+            return RunoffResult::NoWinner;
         }
-        DaoInstruction::Modules { dao_id, module_type, sol_price_usd } => {
-            process_modules(program_id, accounts, dao_id, module_type, sol_price_usd)
+        if self.tallies[*leader as usize] as u128 * 2 > continuing as u128 {
+            return RunoffResult::Winner(*leader);
         }
+        
+        self.eliminated |= 1 << weakest;
+        self.round += 1;
+        self.tallies.iter_mut().for_each(|tally| *tally = 0);
+        self.counted_weight = 0;
+        self.exhausted_weight = 0;
+        RunoffResult::NextRound
     }
 }
 
-// Calculate fee in lamports based on SOL price
-fn calculate_fee_in_lamports(sol_price_usd: u64) -> Result<u64, ProgramError> {
-    // Validate price is within reasonable bounds (e.g., $1-$10,000)
-    if sol_price_usd < 100 || sol_price_usd > 1_000_000 {
-        return Err(DaoError::InvalidSolPrice.into());
+// Option of a proposal, with its tallies
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ProposalOption {
+    pub label: String,
+    pub votes: u64,
+    pub weight: u64,
+}
+
+impl ProposalOption {
+    pub fn new(label: String) -> Self {
+        ProposalOption { label, votes: 0, weight: 0 }
     }
-    
-    // SOL has 9 decimal places (1 SOL = 1_000_000_000 lamports)
-    // sol_price_usd is in cents, so we divide by 100 to get dollars
-    // Calculate how many SOL equals $20 USD
-    let sol_amount = (CREATE_DAO_FEE_USD as u128 * 100 * 1_000_000_000) / sol_price_usd as u128;
-    
-    // Check for overflow or other calculation errors
-    if sol_amount > u64::MAX as u128 {
-        return Err(DaoError::InvalidSolPrice.into());
+}
+
+// Choice made by a voter
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub enum VoteChoice {
+    Option(u8), // Index in the proposal options
+    Abstain,
+    Ranking(Vec<u8>), // Option indexes of a ranked choice proposal, most preferred first
+    Credits(Vec<CreditAllocation>), // Voice credits spent on options in quadratic voting mode
+}
+
+// Voice credits spent on an option by a quadratic ballot
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub struct CreditAllocation {
+    pub option: u8, // Index in the proposal options
+    pub credits: u64,
+}
+
+impl CreditAllocation {
+    // Weight counted for the option: the square root of the credits spent on it
+    pub fn weight(&self) -> u64 {
+        isqrt(self.credits)
     }
-    
-    Ok(sol_amount as u64)
 }
 
-// Calculate featured fee in lamports based on SOL price and number of days
-fn calculate_featured_fee_in_lamports(sol_price_usd: u64, days: u64) -> Result<u64, ProgramError> {
-    // Validate price is within reasonable bounds
-    if sol_price_usd < 100 || sol_price_usd > 1_000_000 {
-        return Err(DaoError::InvalidSolPrice.into());
+impl VoteChoice {
+    // Voice credits spent by a quadratic ballot, 0 for other choices
+    pub fn voice_credits(&self) -> Result<u64, ProgramError> {
+        match self {
+            VoteChoice::Credits(allocations) => allocations
+                .iter()
+                .try_fold(0u64, |total, allocation| total.checked_add(allocation.credits))
+                .ok_or(ProgramError::ArithmeticOverflow),
+            _ => Ok(0),
+        }
     }
-    
-    // Validate days (reasonable limit: 1-365 days)
-    if days == 0 || days > 365 {
-        return Err(ProgramError::InvalidInstructionData);
+}
+
+// Integer square root, rounded down
+pub fn isqrt(value: u64) -> u64 {
+    let value = value as u128;
+    let mut root = value;
+    let mut next = root.div_ceil(2);
+    while next < root {
+        root = next;
+        next = (root + value / root) / 2;
     }
-    
-    // Calculate daily fee: $20 per day
-    let daily_fee_usd = CREATE_DAO_FEE_USD;
-    let total_fee_usd = daily_fee_usd * days;
-    
-    // Calculate SOL amount needed
-    let sol_amount = (total_fee_usd as u128 * 100 * 1_000_000_000) / sol_price_usd as u128;
-    
-    // Check for overflow
-    if sol_amount > u64::MAX as u128 {
-        return Err(DaoError::InvalidSolPrice.into());
+    root as u64
+}
+
+// Funds moved out of the DAO treasury when a proposal is executed
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TreasuryWithdrawal {
+    pub recipient: Pubkey, // Wallet receiving the funds
+    pub mint: Option<Pubkey>, // SPL token mint, or None for lamports
+    pub amount: u64,
+}
+
+// Instruction carried by a proposal, invoked when the proposal is executed
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ProposalInstruction {
+    pub program_id: Pubkey,
+    pub accounts: Vec<ProposalAccountMeta>,
+    pub data: Vec<u8>,
+    pub executed: bool,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProposalAccountMeta {
+    pub pubkey: Pubkey,
+    pub is_signer: bool,
+    pub is_writable: bool,
+}
+
+impl From<&ProposalInstruction> for Instruction {
+    fn from(instruction: &ProposalInstruction) -> Self {
+        Instruction {
+            program_id: instruction.program_id,
+            accounts: instruction
+                .accounts
+                .iter()
+                .map(|meta| AccountMeta {
+                    pubkey: meta.pubkey,
+                    is_signer: meta.is_signer,
+                    is_writable: meta.is_writable,
+                })
+                .collect(),
+            data: instruction.data.clone(),
+        }
     }
-    
-    Ok(sol_amount as u64)
 }
 
-// Process the create DAO instruction
-pub fn process_create_dao(
-    program_id: &Pubkey,
-    accounts: &[AccountInfo],
-    name: String,
-    description: String,
-    discord_server: String,
-    twitter: String,
-    telegram: String,
-    instagram: String,
-    tiktok: String,
-    website: String,
-    treasury: String,
-    profile: String,
-    token_address: String,
-    sol_price_usd: u64,
-) -> ProgramResult {
-    let account_info_iter = &mut accounts.iter();
-    
-    // Get the required accounts
-    let creator_account = next_account_info(account_info_iter)?;
-    let dao_account = next_account_info(account_info_iter)?;
-    let system_program = next_account_info(account_info_iter)?;
-    let fee_account = next_account_info(account_info_iter)?;
-    
-    // Verify the creator is signer
-    if !creator_account.is_signer {
-        return Err(ProgramError::MissingRequiredSignature);
+// Lifecycle of a proposal
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProposalState {
+    Voting,
+    Rejected,
+    Executed,
+    Queued,
+    Cancelled,
+    Tallying, // Waiting for the next instant runoff round to be counted
+}
+
+impl Proposal {
+    // Check that votes are accepted at the given unix timestamp
+    pub fn check_voting_window(&self, now: i64) -> ProgramResult {
+        if now < self.start_time {
+            return Err(DaoError::VotingNotStarted.into());
+        }
+        if now > self.end_time {
+            return Err(DaoError::VotingEnded.into());
+        }
+        Ok(())
     }
     
-    // Verify fee address is correct
-    let expected_fee_pubkey = Pubkey::from_str(FEE_ADDRESS).unwrap();
-    if *fee_account.key != expected_fee_pubkey {
-        return Err(DaoError::InvalidFeeAccount.into());
+    // Check the proposal is queued and its execution delay has elapsed
+    pub fn check_executable(&self, now: i64) -> ProgramResult {
+        if self.state != ProposalState::Queued {
+            return Err(DaoError::ProposalNotPassed.into());
+        }
+        if now < self.executable_at {
+            return Err(DaoError::TimelockNotExpired.into());
+        }
+        Ok(())
     }
     
-    // Calculate fee based on current SOL price
-    let create_dao_fee = calculate_fee_in_lamports(sol_price_usd)?;
-    
-    msg!("DAO creation fee: {} lamports (${} at SOL price of ${}.{})", 
-        create_dao_fee,
-        CREATE_DAO_FEE_USD,
-        sol_price_usd / 100,
-        sol_price_usd % 100
-    );
+    // Account size, with room for the winning option set once the proposal passes
+    pub fn space(&self) -> Result<usize, ProgramError> {
+        Ok(self.try_to_vec()?.len() + usize::from(self.winning_option.is_none()))
+    }
     
-    // Create DAO data structure
-    let dao = Dao {
-        authority: *creator_account.key,
-        name,
-        description,
-        discord_server,
-        twitter,
-        telegram,
-        instagram,
-        tiktok,
-        website,
-        treasury,
-        profile,
-        token_address,
-    };
+    // Option with strictly more weight than any other, if any
+    pub fn leading_option(&self) -> Option<u8> {
+        let max_weight = self.options.iter().map(|option| option.weight).max()?;
+        let mut leaders = self
+            .options
+            .iter()
+            .enumerate()
+            .filter(|(_, option)| option.weight == max_weight);
+        match (leaders.next(), leaders.next()) {
+            (Some((index, _)), None) => Some(index as u8),
+            _ => None,
+        }
+    }
     
-    // Calculate space required for the DAO account
-    let dao_serialized = dao.try_to_vec()?;
-    let space = dao_serialized.len() as u64;
+    // Option carrying the proposal under the given governance rules, if it reaches
+    // the approval threshold: "For" for yes/no proposals, the leading option otherwise
+    pub fn approved_option(&self, governance: &GovernanceConfig) -> Option<u8> {
+        let index = match self.kind {
+            ProposalKind::YesNo => 0,
+            ProposalKind::MultipleChoice => self.leading_option()?,
+            ProposalKind::RankedChoice => return None, // Decided by the runoff
+        };
+        let option_weight: u128 = self.options.iter().map(|option| option.weight as u128).sum();
+        let weight = self.options.get(index as usize)?.weight;
+        let approved = weight > 0
+            && weight as u128 * 10_000 >= option_weight * governance.approval_threshold_bps as u128;
+        approved.then_some(index)
+    }
     
-    // Calculate the rent required
-    let rent = Rent::get()?;
-    let rent_lamports = rent.minimum_balance(space as usize);
+    // Weight of the votes for an option, which for ranked ballots is their first preference
+    pub fn ballot_weight(&self) -> u128 {
+        self.options.iter().map(|option| option.weight as u128).sum()
+    }
     
-    // Check if creator has enough funds for rent + fee
-    if creator_account.lamports() < rent_lamports + create_dao_fee {
-        return Err(DaoError::InsufficientFunds.into());
+    pub fn quorum_reached(&self, governance: &GovernanceConfig) -> bool {
+        let total_weight = self.ballot_weight() + self.weight_abstain as u128;
+        total_weight >= governance.quorum as u128
     }
     
-    // Create the DAO account
-    invoke(
-        &system_instruction::create_account(
-            creator_account.key,
-            dao_account.key,
-            rent_lamports,
-            space,
-            program_id,
-        ),
-        &[creator_account.clone(), dao_account.clone(), system_program.clone()],
-    )?;
+    // Outcome of the proposal under the given governance rules: the option it passes
+    // with, or None when it is rejected
+    pub fn outcome(&self, governance: &GovernanceConfig) -> Option<u8> {
+        if !self.quorum_reached(governance) {
+            return None;
+        }
+        self.approved_option(governance)
+    }
     
-    // Transfer fee to fee account
-    invoke(
-        &system_instruction::transfer(
-            creator_account.key,
-            fee_account.key,
-            create_dao_fee,
-        ),
-        &[creator_account.clone(), fee_account.clone(), system_program.clone()],
-    )?;
+    // Record the final outcome, queueing passed proposals until the execution delay elapses
+    pub fn conclude(
+        &mut self,
+        winning_option: Option<u8>,
+        governance: &GovernanceConfig,
+        now: i64,
+    ) -> ProgramResult {
+        self.winning_option = winning_option;
+        self.state = match winning_option {
+            Some(_) => {
+                self.executable_at = now
+                    .checked_add(governance.execution_delay)
+                    .ok_or(ProgramError::ArithmeticOverflow)?;
+                ProposalState::Queued
+            }
+            None => ProposalState::Rejected,
+        };
+        Ok(())
+    }
     
-    // Serialize and store the DAO data
-    dao.serialize(&mut &mut dao_account.data.borrow_mut()[..])?;
+    // Resolve the current runoff round, concluding the proposal once it has a result
+    pub fn resolve_runoff(&mut self, governance: &GovernanceConfig, now: i64) -> ProgramResult {
+        let runoff = self.runoff.as_mut().ok_or(DaoError::ProposalNotTallying)?;
+        match runoff.resolve() {
+            RunoffResult::Winner(index) => self.conclude(Some(index), governance, now),
+            RunoffResult::NoWinner => self.conclude(None, governance, now),
+            RunoffResult::NextRound => {
+                self.state = ProposalState::Tallying;
+                Ok(())
+            }
+        }
+    }
     
-    msg!("DAO created successfully with ID: {}", dao_account.key);
-    Ok(())
+    // Add a vote to the proposal tallies. The weight of quadratic ballots is the voter's
+    // voice credit budget instead, split across options by the credits spent on them
+    pub fn record_vote(&mut self, choice: &VoteChoice, weight: u64) -> ProgramResult {
+        let ranked = self.kind == ProposalKind::RankedChoice;
+        let (votes, total_weight) = match choice {
+            VoteChoice::Option(index) if !ranked => {
+                let option = self
+                    .options
+                    .get_mut(*index as usize)
+                    .ok_or(DaoError::InvalidVote)?;
+                (&mut option.votes, &mut option.weight)
+            }
+            VoteChoice::Abstain => (&mut self.votes_abstain, &mut self.weight_abstain),
+            VoteChoice::Ranking(ranking) if ranked => {
+                // Rankings list distinct options, and are counted in the first round right away
+                let options = self.options.len();
+                if ranking.is_empty()
+                    || ranking.len() > options
+                    || ranking.iter().any(|index| *index as usize >= options)
+                    || ranking.iter().enumerate().any(|(i, index)| ranking[..i].contains(index))
+                {
+                    return Err(DaoError::InvalidVote.into());
+                }
+                let runoff = self.runoff.as_mut().ok_or(DaoError::InvalidVote)?;
+                runoff.count(ranking, weight)?;
+                let option = &mut self.options[ranking[0] as usize];
+                (&mut option.votes, &mut option.weight)
+            }
+            VoteChoice::Credits(allocations) if !ranked => {
+                // Credits go to distinct options, within the voter's budget. Yes/no ballots
+                // fund a single side, as spending on both would only offset itself
+                let options = self.options.len();
+                if allocations.is_empty()
+                    || (self.kind == ProposalKind::YesNo && allocations.len() > 1)
+                    || allocations.iter().any(|allocation| {
+                        allocation.credits == 0 || allocation.option as usize >= options
+                    })
+                    || allocations.iter().enumerate().any(|(i, allocation)| {
+                        allocations[..i].iter().any(|other| other.option == allocation.option)
+                    })
+                {
+                    return Err(DaoError::InvalidVote.into());
+                }
+                if choice.voice_credits()? > weight {
+                    return Err(DaoError::VoiceCreditsExceeded.into());
+                }
+                
+                for allocation in allocations {
+                    let option = &mut self.options[allocation.option as usize];
+                    option.votes =
+                        option.votes.checked_add(1).ok_or(ProgramError::ArithmeticOverflow)?;
+                    option.weight = option
+                        .weight
+                        .checked_add(allocation.weight())
+                        .ok_or(ProgramError::ArithmeticOverflow)?;
+                }
+                return Ok(());
+            }
+            _ => return Err(DaoError::InvalidVote.into()),
+        };
+        *votes = votes.checked_add(1).ok_or(ProgramError::ArithmeticOverflow)?;
+        *total_weight = total_weight
+            .checked_add(weight)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        Ok(())
+    }
 }
 
-// Process the create proposal instruction
-pub fn process_create_proposal(
-    program_id: &Pubkey,
-    accounts: &[AccountInfo],
-    name: String,
-    description: String,
-    dao_id: String,
-    pod_id: String,
-    start_time: i64,
-    end_time: i64,
-) -> ProgramResult {
-    let account_info_iter = &mut accounts.iter();
-    
-    // Get the required accounts
-    let creator_account = next_account_info(account_info_iter)?;
-    let proposal_account = next_account_info(account_info_iter)?;
-    let dao_account = next_account_info(account_info_iter)?;
-    let system_program = next_account_info(account_info_iter)?;
-    
-    // Get fee account if provided (fees for proposal creation are optional)
-    let fee_account = if account_info_iter.len() > 0 {
-        Some(next_account_info(account_info_iter)?)
-    } else {
-        None
-    };
-    
-    // Verify the creator is signer
-    if !creator_account.is_signer {
-        return Err(ProgramError::MissingRequiredSignature);
+// Vote account data structure
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct Vote {
+    pub account_type: AccountType,
+    pub voter: Pubkey,
+    pub choice: VoteChoice,
+    pub proposal_id: String,
+    pub weight: u64,
+    pub counted_round: u8, // Last runoff round a ranked ballot was counted in, 0 otherwise
+    pub credits_spent: u64, // Voice credits spent by a quadratic ballot, 0 otherwise
+    pub locked_amount: u64, // Governance tokens escrowed until the proposal is finalized
+    pub locked_mint: Option<Pubkey>, // Mint of the escrowed tokens, None if nothing was locked
+}
+
+// Featured account data structure
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct Featured {
+    pub account_type: AccountType,
+    pub authority: Pubkey,
+    pub dao_id: Pubkey,
+    pub days: u64, // Total number of days paid for
+    pub starts_at: i64,
+    pub expires_at: i64,
+}
+
+impl Featured {
+    // Whether the DAO is still featured at the given time
+    pub fn is_active(&self, now: i64) -> bool {
+        self.starts_at <= now && now < self.expires_at
     }
     
-    // Verify time constraints
-    let clock = Clock::get()?;
-    if start_time < clock.unix_timestamp || end_time <= start_time {
-        return Err(DaoError::ProposalTimeInvalid.into());
+    // Add days to the listing, restarting it from now if it has expired
+    pub fn extend(&mut self, now: i64, days: u64) -> ProgramResult {
+        let duration = i64::try_from(days)
+            .ok()
+            .and_then(|days| days.checked_mul(SECONDS_PER_DAY))
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        if !self.is_active(now) {
+            self.starts_at = now;
+            self.expires_at = now;
+        }
+        self.expires_at = self
+            .expires_at
+            .checked_add(duration)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        self.days = self.days.checked_add(days).ok_or(ProgramError::ArithmeticOverflow)?;
+        Ok(())
     }
     
-    // Create proposal data structure
-    let proposal = Proposal {
-        authority: *creator_account.key,
-        name,
-        description,
-        dao_id,
-        pod_id,
-        start_time,
-        end_time,
-    };
+    // Feature the DAO from `starts_at` to `expires_at`, merging the window into the listing
+    // when they overlap or follow each other. A separate window only replaces the listing once
+    // it has expired, and a window that is already over leaves the listing as it is
+    pub fn schedule(
+        &mut self,
+        now: i64,
+        starts_at: i64,
+        expires_at: i64,
+        days: u64,
+    ) -> ProgramResult {
+        if starts_at <= self.expires_at && self.starts_at <= expires_at {
+            self.starts_at = self.starts_at.min(starts_at);
+            self.expires_at = self.expires_at.max(expires_at);
+        } else if self.expires_at <= now {
+            self.starts_at = starts_at;
+            self.expires_at = expires_at;
+        } else if now < expires_at {
+            return Err(DaoError::FeaturedScheduleConflict.into());
+        }
+        self.days = self.days.checked_add(days).ok_or(ProgramError::ArithmeticOverflow)?;
+        Ok(())
+    }
+}
+
+// Auction of the featured slots of one period
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct FeaturedAuction {
+    pub account_type: AccountType,
+    pub period: u64,
+    pub slots: u8,
+    pub days: u64, // Length of the period, in days
+    pub starts_at: i64, // Start of the featured period, when bidding closes
+    pub ends_at: i64,
+    pub settled: bool,
+    pub opener: Pubkey, // Bidder who paid the auction's rent
+    pub open_bids: u32, // Bid accounts not yet claimed or refunded
+    pub leaders: Vec<FeaturedBidEntry>, // Highest bids, best first, at most `slots`
+}
+
+// Total bid of a DAO in an auction
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FeaturedBidEntry {
+    pub dao_id: Pubkey,
+    pub amount: u64,
+}
+
+impl FeaturedAuction {
+    // Open the auction of a period, which only takes bids during the previous period
+    pub fn open(
+        config: &FeaturedAuctionConfig,
+        period: u64,
+        opener: Pubkey,
+        now: i64,
+    ) -> Result<Self, ProgramError> {
+        if config.slots == 0 || period != config.period_at(now)?.saturating_add(1) {
+            return Err(DaoError::AuctionClosed.into());
+        }
+        let period_secs = config.period_secs()?;
+        let starts_at = i64::try_from(period)
+            .ok()
+            .and_then(|period| period.checked_mul(period_secs))
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        Ok(Self {
+            account_type: AccountType::FeaturedAuction,
+            period,
+            slots: config.slots,
+            days: config.period_days,
+            starts_at,
+            ends_at: starts_at
+                .checked_add(period_secs)
+                .ok_or(ProgramError::ArithmeticOverflow)?,
+            settled: false,
+            opener,
+            open_bids: 0,
+            leaders: vec![],
+        })
+    }
     
-    // Calculate space required for the proposal account
-    let proposal_serialized = proposal.try_to_vec()?;
-    let space = proposal_serialized.len() as u64;
+    // Account size once every slot has a bid
+    pub fn max_space(&self) -> usize {
+        let entry = FeaturedBidEntry { dao_id: Pubkey::default(), amount: 0 };
+        let full = FeaturedAuction {
+            leaders: vec![entry; self.slots as usize],
+            ..*self
+        };
+        full.try_to_vec().map(|data| data.len()).unwrap_or_default()
+    }
     
-    // Calculate the rent required
-    let rent = Rent::get()?;
-    let rent_lamports = rent.minimum_balance(space as usize);
+    // Account for a bid account that was claimed or refunded
+    pub fn close_bid(&mut self) -> ProgramResult {
+        self.open_bids = self.open_bids.checked_sub(1).ok_or(ProgramError::ArithmeticOverflow)?;
+        Ok(())
+    }
     
-    // Create the proposal account
-    invoke(
-        &system_instruction::create_account(
-            creator_account.key,
-            proposal_account.key,
-            rent_lamports,
-            space,
-            program_id,
-        ),
-        &[creator_account.clone(), proposal_account.clone(), system_program.clone()],
-    )?;
+    // Whether the DAO holds one of the slots
+    pub fn is_winning(&self, dao_id: &Pubkey) -> bool {
+        self.leaders.iter().any(|entry| entry.dao_id == *dao_id)
+    }
     
-    // If fee account is provided, send a small fee (optional)
-    if let Some(fee_acc) = fee_account {
-        // Verify fee address is correct
-        let expected_fee_pubkey = Pubkey::from_str(FEE_ADDRESS).unwrap();
-        if *fee_acc.key == expected_fee_pubkey {
-            // Send a small fee (0.01 SOL)
-            invoke(
-                &system_instruction::transfer(
-                    creator_account.key,
-                    fee_acc.key,
-                    10_000_000, // 0.01 SOL in lamports
-                ),
-                &[creator_account.clone(), fee_acc.clone(), system_program.clone()],
-            )?;
+    // Rank a DAO's total bid among the leaders, failing if it doesn't get a slot
+    pub fn place_bid(&mut self, dao_id: Pubkey, amount: u64) -> ProgramResult {
+        self.leaders.retain(|entry| entry.dao_id != dao_id);
+        
+        // Ties go to the earlier bid
+        let rank = self
+            .leaders
+            .iter()
+            .position(|entry| amount > entry.amount)
+            .unwrap_or(self.leaders.len());
+        if rank >= self.slots as usize {
+            return Err(DaoError::BidTooLow.into());
         }
+        self.leaders.insert(rank, FeaturedBidEntry { dao_id, amount });
+        self.leaders.truncate(self.slots as usize);
+        Ok(())
     }
-    
-    // Serialize and store the proposal data
-    proposal.serialize(&mut &mut proposal_account.data.borrow_mut()[..])?;
-    
-    msg!("Proposal created successfully with ID: {}", proposal_account.key);
-    Ok(())
 }
 
-// Process the vote instruction
-pub fn process_vote(
-    program_id: &Pubkey,
-    accounts: &[AccountInfo],
-    vote: String,
-    proposal_id: String,
-) -> ProgramResult {
-    let account_info_iter = &mut accounts.iter();
-    
-    // Get the required accounts
-    let voter_account = next_account_info(account_info_iter)?;
-    let vote_account = next_account_info(account_info_iter)?;
-    let proposal_account = next_account_info(account_info_iter)?;
-    let system_program = next_account_info(account_info_iter)?;
+// Bid of a DAO in a featured slot auction, escrowing its lamports
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct FeaturedBid {
+    pub account_type: AccountType,
+    pub auction: Pubkey,
+    pub dao_id: Pubkey,
+    pub bidder: Pubkey,
+    pub amount: u64, // Escrowed lamports, on top of the account's rent
+}
+
+// DAO extensions that can be enabled through the Modules instruction
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModuleType {
+    Pod, // Teams
+    Pol, // Proof-Of-Love
+}
+
+// Per-DAO registry of the enabled modules
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct ModuleRegistry {
+    pub account_type: AccountType,
+    pub dao_id: Pubkey,
+    pub pod_enabled: bool,
+    pub pol_enabled: bool,
+}
+
+impl ModuleRegistry {
+    // Whether the DAO has the given module enabled
+    pub fn is_enabled(&self, module_type: ModuleType) -> bool {
+        match module_type {
+            ModuleType::Pod => self.pod_enabled,
+            ModuleType::Pol => self.pol_enabled,
+        }
+    }
     
-    // Get fee account if provided (fees for voting are optional)
-    let fee_account = if account_info_iter.len() > 0 {
-        Some(next_account_info(account_info_iter)?)
-    } else {
-        None
-    };
+    // Enable a module, failing if it already is
+    pub fn enable(&mut self, module_type: ModuleType) -> ProgramResult {
+        if self.is_enabled(module_type) {
+            return Err(DaoError::ModuleAlreadyEnabled.into());
+        }
+        match module_type {
+            ModuleType::Pod => self.pod_enabled = true,
+            ModuleType::Pol => self.pol_enabled = true,
+        }
+        Ok(())
+    }
     
-    // Verify the voter is signer
-    if !voter_account.is_signer {
-        return Err(ProgramError::MissingRequiredSignature);
+    // Disable a module, failing if it isn't enabled
+    pub fn disable(&mut self, module_type: ModuleType) -> ProgramResult {
+        if !self.is_enabled(module_type) {
+            return Err(DaoError::ModuleNotEnabled.into());
+        }
+        match module_type {
+            ModuleType::Pod => self.pod_enabled = false,
+            ModuleType::Pol => self.pol_enabled = false,
+        }
+        Ok(())
+    }
+}
+
+// Team within a DAO, that proposals can be made for
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct Pod {
+    pub account_type: AccountType,
+    pub dao_id: Pubkey,
+    pub authority: Pubkey, // Manages the pod's members
+    pub name: String,
+    pub members: Vec<Pubkey>, // At most `MAX_POD_MEMBERS`
+}
+
+impl Pod {
+    // Account size once the pod is full
+    pub fn max_space(&self) -> usize {
+        let full = Pod {
+            account_type: self.account_type,
+            dao_id: self.dao_id,
+            authority: self.authority,
+            name: self.name.clone(),
+            members: vec![Pubkey::default(); MAX_POD_MEMBERS],
+        };
+        full.try_to_vec().map(|data| data.len()).unwrap_or_default()
     }
     
-    // Validate the vote
-    if vote != "for" && vote != "against" {
-        return Err(DaoError::InvalidVote.into());
+    pub fn is_member(&self, member: &Pubkey) -> bool {
+        self.members.contains(member)
     }
     
-    // Create vote data structure
-    let vote_data = Vote {
-        voter: *voter_account.key,
-        vote,
-        proposal_id,
-    };
+    pub fn add_member(&mut self, member: Pubkey) -> ProgramResult {
+        if self.is_member(&member) {
+            return Err(DaoError::AlreadyPodMember.into());
+        }
+        if self.members.len() >= MAX_POD_MEMBERS {
+            return Err(DaoError::PodFull.into());
+        }
+        self.members.push(member);
+        Ok(())
+    }
     
-    // Calculate space required for the vote account
-    let vote_serialized = vote_data.try_to_vec()?;
-    let space = vote_serialized.len() as u64;
+    pub fn remove_member(&mut self, member: &Pubkey) -> ProgramResult {
+        let index = self
+            .members
+            .iter()
+            .position(|key| key == member)
+            .ok_or(DaoError::NotPodMember)?;
+        self.members.remove(index);
+        Ok(())
+    }
+}
+
+// Proof-Of-Love reputation of a DAO member, built from endorsements
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct Reputation {
+    pub account_type: AccountType,
+    pub dao_id: Pubkey,
+    pub member: Pubkey,
+    pub score: u64, // As of `updated_at`, decays over time
+    pub updated_at: i64,
+    pub endorsements_received: u64,
+    pub last_endorsed_at: i64, // Last endorsement given by the member, 0 if none
+}
+
+impl Reputation {
+    pub fn new(dao_id: Pubkey, member: Pubkey, now: i64) -> Self {
+        Reputation {
+            account_type: AccountType::Reputation,
+            dao_id,
+            member,
+            score: 0,
+            updated_at: now,
+            endorsements_received: 0,
+            last_endorsed_at: 0,
+        }
+    }
     
-    // Calculate the rent required
-    let rent = Rent::get()?;
-    let rent_lamports = rent.minimum_balance(space as usize);
+    // Score at the given time: halved every `REPUTATION_HALF_LIFE`, linearly in between
+    pub fn score_at(&self, now: i64) -> u64 {
+        let elapsed = now.saturating_sub(self.updated_at).max(0) as u64;
+        let half_life = REPUTATION_HALF_LIFE as u64;
+        let halvings = elapsed / half_life;
+        if halvings >= u64::BITS as u64 {
+            return 0;
+        }
+        let halved = self.score >> halvings;
+        let decay = (halved / 2) as u128 * (elapsed % half_life) as u128 / half_life as u128;
+        halved - decay as u64
+    }
     
-    // Create the vote account
-    invoke(
-        &system_instruction::create_account(
-            voter_account.key,
-            vote_account.key,
-            rent_lamports,
-            space,
-            program_id,
-        ),
-        &[voter_account.clone(), vote_account.clone(), system_program.clone()],
-    )?;
+    // Record an endorsement received, on top of the decayed score
+    pub fn endorse(&mut self, now: i64) -> ProgramResult {
+        self.score = self
+            .score_at(now)
+            .checked_add(ENDORSEMENT_POINTS)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        self.updated_at = now;
+        self.endorsements_received = self.endorsements_received.saturating_add(1);
+        Ok(())
+    }
     
-    // If fee account is provided, send a small fee (optional)
-    if let Some(fee_acc) = fee_account {
-        // Verify fee address is correct
-        let expected_fee_pubkey = Pubkey::from_str(FEE_ADDRESS).unwrap();
-        if *fee_acc.key == expected_fee_pubkey {
-            // Send a small fee (0.005 SOL)
-            invoke(
-                &system_instruction::transfer(
-                    voter_account.key,
-                    fee_acc.key,
-                    5_000_000, // 0.005 SOL in lamports
-                ),
-                &[voter_account.clone(), fee_acc.clone(), system_program.clone()],
-            )?;
+    // Record an endorsement given, at most once per `ENDORSEMENT_COOLDOWN`
+    pub fn record_endorsement_given(&mut self, now: i64) -> ProgramResult {
+        if self.last_endorsed_at != 0 && now < self.last_endorsed_at + ENDORSEMENT_COOLDOWN {
+            return Err(DaoError::EndorsementRateLimited.into());
+        }
+        self.last_endorsed_at = now;
+        Ok(())
+    }
+}
+
+// Last endorsement of a member by a given endorser, limiting repeat endorsements
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct Endorsement {
+    pub account_type: AccountType,
+    pub dao_id: Pubkey,
+    pub endorser: Pubkey,
+    pub member: Pubkey,
+    pub endorsed_at: i64, // 0 if the member was never endorsed by the endorser
+}
+
+impl Endorsement {
+    pub fn new(dao_id: Pubkey, endorser: Pubkey, member: Pubkey) -> Self {
+        Endorsement {
+            account_type: AccountType::Endorsement,
+            dao_id,
+            endorser,
+            member,
+            endorsed_at: 0,
         }
     }
     
-    // Serialize and store the vote data
-    vote_data.serialize(&mut &mut vote_account.data.borrow_mut()[..])?;
-    
-    msg!("Vote recorded successfully");
-    Ok(())
+    // Record the endorsement, at most once per `ENDORSEMENT_REPEAT_COOLDOWN`
+    pub fn record(&mut self, now: i64) -> ProgramResult {
+        if self.endorsed_at != 0 && now < self.endorsed_at + ENDORSEMENT_REPEAT_COOLDOWN {
+            return Err(DaoError::EndorsementRateLimited.into());
+        }
+        self.endorsed_at = now;
+        Ok(())
+    }
+}
+
+// Global program config, set by the admin
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct Config {
+    pub account_type: AccountType,
+    pub admin: Pubkey,
+    pub fee_recipient: Pubkey,
+    pub fees: FeeSchedule,
+    pub stable_mints: Vec<Pubkey>,
+    pub fee_split: FeeSplit,
+    pub featured_auction: FeaturedAuctionConfig,
+    pub featured_tiers: Vec<FeaturedTier>,
+}
+
+// Platform fees charged for each paid action, in USD cents
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FeeSchedule {
+    pub create_dao: u64,
+    pub featured_per_day: u64,
+    pub pod_module: u64,
+    pub pol_module: u64,
+    pub create_proposal: u64,
+    pub vote: u64,
+}
+
+// Shares of the platform fees paid out to other parties, in basis points
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FeeSplit {
+    pub referrer_bps: u16, // Share of DAO creation fees sent to the referrer, if any
+    pub treasury_rebate_bps: u16, // Share of featured and module fees rebated to the DAO treasury
+}
+
+// Featured slots auctioned per period, instead of selling featured days directly
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FeaturedAuctionConfig {
+    pub slots: u8, // Number of DAOs featured per period, 0 to sell featured days directly
+    pub period_days: u64, // Length of a period, in days
+}
+
+// Daily featured rate applied from a minimum number of days, e.g. weekly or monthly rates
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FeaturedTier {
+    pub min_days: u64,
+    pub per_day: u64, // In USD cents
+}
+
+impl FeaturedAuctionConfig {
+    // Length of a period, in seconds
+    pub fn period_secs(&self) -> Result<i64, ProgramError> {
+        i64::try_from(self.period_days)
+            .ok()
+            .and_then(|days| days.checked_mul(SECONDS_PER_DAY))
+            .filter(|secs| *secs > 0)
+            .ok_or(DaoError::InvalidConfig.into())
+    }
+    
+    // Index of the period containing the given time
+    pub fn period_at(&self, now: i64) -> Result<u64, ProgramError> {
+        let period_secs = self.period_secs()?;
+        Ok(now.max(0) as u64 / period_secs as u64)
+    }
+}
+
+impl Config {
+    // Check the config values are within bounds
+    pub fn validate(&self) -> ProgramResult {
+        if self.stable_mints.len() > MAX_STABLE_MINTS {
+            return Err(DaoError::InvalidConfig.into());
+        }
+        if self.fee_split.referrer_bps > 10_000 || self.fee_split.treasury_rebate_bps > 10_000 {
+            return Err(DaoError::InvalidConfig.into());
+        }
+        let auction = &self.featured_auction;
+        if auction.slots > MAX_FEATURED_SLOTS
+            || (auction.slots > 0 && !(1..=365).contains(&auction.period_days))
+        {
+            return Err(DaoError::InvalidConfig.into());
+        }
+        
+        // Tiers apply from 2 to 365 days, sorted by duration
+        let tiers = &self.featured_tiers;
+        if tiers.len() > MAX_FEATURED_TIERS
+            || tiers.iter().any(|tier| !(2..=365).contains(&tier.min_days))
+            || tiers.windows(2).any(|pair| pair[0].min_days >= pair[1].min_days)
+        {
+            return Err(DaoError::InvalidConfig.into());
+        }
+        Ok(())
+    }
+    
+    // Verify an account is the configured fee recipient
+    pub fn check_fee_account(&self, fee_account: &AccountInfo) -> ProgramResult {
+        if *fee_account.key != self.fee_recipient {
+            return Err(DaoError::InvalidFeeAccount.into());
+        }
+        Ok(())
+    }
+}
+
+impl FeeSchedule {
+    // Fee for enabling a module of the given type
+    pub fn module_fee(&self, module_type: ModuleType) -> u64 {
+        match module_type {
+            ModuleType::Pod => self.pod_module,
+            ModuleType::Pol => self.pol_module,
+        }
+    }
+}
+
+macro_rules! impl_program_account {
+    ($($account:ty => $account_type:ident),* $(,)?) => {
+        $(
+            impl ProgramAccount for $account {
+                const ACCOUNT_TYPE: AccountType = AccountType::$account_type;
+                
+                fn account_type(&self) -> AccountType {
+                    self.account_type
+                }
+            }
+        )*
+    };
+}
+
+impl_program_account! {
+    Dao => Dao,
+    Proposal => Proposal,
+    Vote => Vote,
+    Featured => Featured,
+    ModuleRegistry => Module,
+    Config => Config,
+    FeaturedAuction => FeaturedAuction,
+    FeaturedBid => FeaturedBid,
+    Pod => Pod,
+    Reputation => Reputation,
+    Endorsement => Endorsement,
+}
+
+// Load a program account, checking its owner and discriminator
+pub fn load_account<T: ProgramAccount>(
+    program_id: &Pubkey,
+    account: &AccountInfo,
+) -> Result<T, ProgramError> {
+    if account.owner != program_id {
+        return Err(DaoError::InvalidAccountOwner.into());
+    }
+    let data = T::deserialize(&mut &account.data.borrow()[..])
+        .map_err(|_| DaoError::InvalidAccountType)?;
+    if data.account_type() != T::ACCOUNT_TYPE {
+        return Err(DaoError::InvalidAccountType.into());
+    }
+    Ok(data)
+}
+
+// Load the DAO account and check it is the DAO referenced by the instruction
+fn load_dao(
+    program_id: &Pubkey,
+    dao_account: &AccountInfo,
+    dao_id: &Pubkey,
+) -> Result<Dao, ProgramError> {
+    if dao_account.key != dao_id {
+        return Err(DaoError::DaoMismatch.into());
+    }
+    load_account(program_id, dao_account)
+}
+
+// Check the DAO has a module enabled in its module registry
+fn check_module_enabled(
+    program_id: &Pubkey,
+    module_account: &AccountInfo,
+    dao_id: &Pubkey,
+    module_type: ModuleType,
+) -> ProgramResult {
+    let (expected_module_pubkey, _) = pda::find_module_address(program_id, dao_id);
+    if *module_account.key != expected_module_pubkey {
+        return Err(DaoError::InvalidAccountAddress.into());
+    }
+    
+    // DAOs that never enabled a module have no registry
+    if module_account.owner != program_id {
+        return Err(DaoError::ModuleNotEnabled.into());
+    }
+    let registry: ModuleRegistry = load_account(program_id, module_account)?;
+    if !registry.is_enabled(module_type) {
+        return Err(DaoError::ModuleNotEnabled.into());
+    }
+    Ok(())
+}
+
+// Current reputation score of a DAO member, 0 if they have no reputation account
+pub fn reputation_score(
+    program_id: &Pubkey,
+    reputation_account: &AccountInfo,
+    dao_id: &Pubkey,
+    member: &Pubkey,
+    now: i64,
+) -> Result<u64, ProgramError> {
+    let (expected_reputation_pubkey, _) = pda::find_reputation_address(program_id, dao_id, member);
+    if *reputation_account.key != expected_reputation_pubkey {
+        return Err(DaoError::InvalidAccountAddress.into());
+    }
+    if reputation_account.owner != program_id {
+        return Ok(0);
+    }
+    let reputation: Reputation = load_account(program_id, reputation_account)?;
+    Ok(reputation.score_at(now))
+}
+
+// Load a member's reputation, creating its account on first use
+fn load_or_create_reputation<'a>(
+    program_id: &Pubkey,
+    payer: &AccountInfo<'a>,
+    reputation_account: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    dao_id: &Pubkey,
+    member: &Pubkey,
+    now: i64,
+) -> Result<Reputation, ProgramError> {
+    let (expected_reputation_pubkey, reputation_bump) =
+        pda::find_reputation_address(program_id, dao_id, member);
+    if *reputation_account.key != expected_reputation_pubkey {
+        return Err(DaoError::InvalidAccountAddress.into());
+    }
+    if reputation_account.owner == program_id {
+        return load_account(program_id, reputation_account);
+    }
+    
+    let reputation = Reputation::new(*dao_id, *member, now);
+    let space = reputation.try_to_vec()?.len();
+    create_pda_account(
+        payer,
+        reputation_account,
+        system_program,
+        program_id,
+        Rent::get()?.minimum_balance(space),
+        space as u64,
+        &[pda::REPUTATION_SEED, dao_id.as_ref(), member.as_ref(), &[reputation_bump]],
+    )?;
+    Ok(reputation)
+}
+
+// Load the endorsements of a member by an endorser, creating the account on the first one
+fn load_or_create_endorsement<'a>(
+    program_id: &Pubkey,
+    payer: &AccountInfo<'a>,
+    endorsement_account: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    dao_id: &Pubkey,
+    endorser: &Pubkey,
+    member: &Pubkey,
+) -> Result<Endorsement, ProgramError> {
+    let (expected_endorsement_pubkey, endorsement_bump) =
+        pda::find_endorsement_address(program_id, dao_id, endorser, member);
+    if *endorsement_account.key != expected_endorsement_pubkey {
+        return Err(DaoError::InvalidAccountAddress.into());
+    }
+    if endorsement_account.owner == program_id {
+        return load_account(program_id, endorsement_account);
+    }
+    
+    let endorsement = Endorsement::new(*dao_id, *endorser, *member);
+    let space = endorsement.try_to_vec()?.len();
+    create_pda_account(
+        payer,
+        endorsement_account,
+        system_program,
+        program_id,
+        Rent::get()?.minimum_balance(space),
+        space as u64,
+        &[
+            pda::ENDORSEMENT_SEED,
+            dao_id.as_ref(),
+            endorser.as_ref(),
+            member.as_ref(),
+            &[endorsement_bump],
+        ],
+    )?;
+    Ok(endorsement)
+}
+
+// Load the global program config, checking it lives at its PDA
+fn load_config(program_id: &Pubkey, config_account: &AccountInfo) -> Result<Config, ProgramError> {
+    let (expected_config_pubkey, _) = pda::find_config_address(program_id);
+    if *config_account.key != expected_config_pubkey {
+        return Err(DaoError::InvalidAccountAddress.into());
+    }
+    load_account(program_id, config_account)
+}
+
+// Program entrypoint processor
+pub fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let instruction = DaoInstruction::try_from_slice(instruction_data)
+        .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+    match instruction {
+        DaoInstruction::CreateDao {
+            name,
+            description,
+            discord_server,
+            twitter,
+            telegram,
+            instagram,
+            tiktok,
+            website,
+            profile,
+            token_address,
+            governance,
+            fee_mint,
+            referrer,
+        } => {
+            process_create_dao(
+                program_id,
+                accounts,
+                name,
+                description,
+                discord_server,
+                twitter,
+                telegram,
+                instagram,
+                tiktok,
+                website,
+                profile,
+                token_address,
+                governance,
+                fee_mint,
+                referrer,
+            )
+        }
+        DaoInstruction::CreateProposal {
+            name,
+            description,
+            dao_id,
+            pod_id,
+            start_time,
+            end_time,
+            withdrawal,
+            instructions,
+            options,
+            ranked,
+        } => {
+            process_create_proposal(
+                program_id,
+                accounts,
+                name,
+                description,
+                dao_id,
+                pod_id,
+                start_time,
+                end_time,
+                withdrawal,
+                instructions,
+                options,
+                ranked,
+            )
+        }
+        DaoInstruction::Vote { choice, proposal_id } => {
+            process_vote(program_id, accounts, choice, proposal_id)
+        }
+        DaoInstruction::Featured { dao_id, days, fee_mint } => {
+            process_featured(program_id, accounts, dao_id, days, fee_mint)
+        }
+        DaoInstruction::RenewFeatured { dao_id, days, fee_mint } => {
+            process_renew_featured(program_id, accounts, dao_id, days, fee_mint)
+        }
+        DaoInstruction::CloseExpiredFeatured => {
+            process_close_expired_featured(program_id, accounts)
+        }
+        DaoInstruction::PlaceFeaturedBid { dao_id, period, amount } => {
+            process_place_featured_bid(program_id, accounts, dao_id, period, amount)
+        }
+        DaoInstruction::SettleFeaturedAuction => {
+            process_settle_featured_auction(program_id, accounts)
+        }
+        DaoInstruction::ClaimFeaturedSlot => {
+            process_claim_featured_slot(program_id, accounts)
+        }
+        DaoInstruction::RefundFeaturedBid => {
+            process_refund_featured_bid(program_id, accounts)
+        }
+        DaoInstruction::UpdateDaoPermissions { module_manager, allow_featured_sponsors } => {
+            process_update_dao_permissions(
+                program_id,
+                accounts,
+                module_manager,
+                allow_featured_sponsors,
+            )
+        }
+        DaoInstruction::DisableModule { dao_id, module_type } => {
+            process_disable_module(program_id, accounts, dao_id, module_type)
+        }
+        DaoInstruction::CreatePod { dao_id, name } => {
+            process_create_pod(program_id, accounts, dao_id, name)
+        }
+        DaoInstruction::AddPodMember { member } => {
+            process_add_pod_member(program_id, accounts, member)
+        }
+        DaoInstruction::RemovePodMember { member } => {
+            process_remove_pod_member(program_id, accounts, member)
+        }
+        DaoInstruction::Endorse { dao_id, member } => {
+            process_endorse(program_id, accounts, dao_id, member)
+        }
+        DaoInstruction::CountRankedVotes => {
+            process_count_ranked_votes(program_id, accounts)
+        }
+        DaoInstruction::CloseFeaturedAuction => {
+            process_close_featured_auction(program_id, accounts)
+        }
+        DaoInstruction::ReleaseVoteTokens => {
+            process_release_vote_tokens(program_id, accounts)
+        }
+        DaoInstruction::Modules { dao_id, module_type, fee_mint } => {
+            process_modules(program_id, accounts, dao_id, module_type, fee_mint)
+        }
+        DaoInstruction::FinalizeProposal => {
+            process_finalize_proposal(program_id, accounts)
+        }
+        DaoInstruction::Deposit { amount } => {
+            process_deposit(program_id, accounts, amount)
+        }
+        DaoInstruction::DepositToken { amount } => {
+            process_deposit_token(program_id, accounts, amount)
+        }
+        DaoInstruction::ExecuteProposal => {
+            process_execute_proposal(program_id, accounts)
+        }
+        DaoInstruction::CancelProposal => {
+            process_cancel_proposal(program_id, accounts)
+        }
+        DaoInstruction::InitializeConfig {
+            admin,
+            fee_recipient,
+            fees,
+            stable_mints,
+            fee_split,
+            featured_auction,
+            featured_tiers,
+        } => {
+            process_initialize_config(
+                program_id,
+                accounts,
+                admin,
+                fee_recipient,
+                fees,
+                stable_mints,
+                fee_split,
+                featured_auction,
+                featured_tiers,
+            )
+        }
+        DaoInstruction::UpdateConfig {
+            admin,
+            fee_recipient,
+            fees,
+            stable_mints,
+            fee_split,
+            featured_auction,
+            featured_tiers,
+        } => {
+            process_update_config(
+                program_id,
+                accounts,
+                admin,
+                fee_recipient,
+                fees,
+                stable_mints,
+                fee_split,
+                featured_auction,
+                featured_tiers,
+            )
+        }
+    }
+}
+
+// Calculate the lamports worth a fee in USD cents based on SOL price
+fn calculate_fee_in_lamports(fee_usd_cents: u64, sol_price_usd: u64) -> Result<u64, ProgramError> {
+    // Validate price is within reasonable bounds (e.g., $1-$10,000)
+    if !(100..=1_000_000).contains(&sol_price_usd) {
+        return Err(DaoError::InvalidSolPrice.into());
+    }
+    
+    // SOL has 9 decimal places (1 SOL = 1_000_000_000 lamports)
+    // Both the fee and sol_price_usd are in cents
+    let sol_amount = (fee_usd_cents as u128 * 1_000_000_000) / sol_price_usd as u128;
+    
+    // Check for overflow or other calculation errors
+    if sol_amount > u64::MAX as u128 {
+        return Err(DaoError::InvalidSolPrice.into());
+    }
+    
+    Ok(sol_amount as u64)
+}
+
+// Calculate featured fee in USD cents based on number of days, at the daily rate of the
+// longest tier the duration reaches, or the base daily fee below every tier
+fn calculate_featured_fee_usd_cents(
+    fee_per_day_usd_cents: u64,
+    tiers: &[FeaturedTier],
+    days: u64,
+) -> Result<u64, ProgramError> {
+    // Validate days (reasonable limit: 1-365 days)
+    if days == 0 || days > 365 {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    
+    let per_day = match tiers.iter().rev().find(|tier| days >= tier.min_days) {
+        Some(tier) => {
+            msg!(
+                "Featured tier applied: {}+ days at {} cents per day",
+                tier.min_days,
+                tier.per_day
+            );
+            tier.per_day
+        }
+        None => fee_per_day_usd_cents,
+    };
+    per_day.checked_mul(days).ok_or(ProgramError::ArithmeticOverflow)
+}
+
+// Calculate the amount of a USD stablecoin worth a fee in USD cents
+fn calculate_fee_in_stable_tokens(fee_usd_cents: u64, decimals: u8) -> Result<u64, ProgramError> {
+    // Mints with fewer than 2 decimals can't represent cents exactly
+    let scale = decimals.checked_sub(2).ok_or(DaoError::InvalidFeeMint)?;
+    10u64
+        .checked_pow(scale as u32)
+        .and_then(|factor| fee_usd_cents.checked_mul(factor))
+        .ok_or(ProgramError::ArithmeticOverflow)
+}
+
+// Split a fee into the platform's part and a share of `share_bps` basis points.
+// The share is rounded down and the platform gets the rest, so both always add up to the fee.
+fn split_fee(amount: u64, share_bps: u16) -> (u64, u64) {
+    let share = (amount as u128 * share_bps.min(10_000) as u128 / 10_000) as u64;
+    (amount - share, share)
+}
+
+// Price the fee for featuring a DAO for `days`, returning it with the DAO treasury's account
+// receiving the rebate
+#[allow(clippy::too_many_arguments)]
+fn featured_fee<'a, 'b, I: Iterator<Item = &'b AccountInfo<'a>>>(
+    program_id: &Pubkey,
+    config: &Config,
+    dao_account: &AccountInfo<'a>,
+    days: u64,
+    fee_mint: Option<Pubkey>,
+    payer: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    fee_account: &'b AccountInfo<'a>,
+    fee_source_account: &'b AccountInfo<'a>,
+    account_info_iter: &mut I,
+) -> Result<(FeePayment<'a, 'b>, &'b AccountInfo<'a>), ProgramError> {
+    let total_usd_fee = calculate_featured_fee_usd_cents(
+        config.fees.featured_per_day,
+        &config.featured_tiers,
+        days,
+    )?;
+    let feature_fee = FeePayment::new(
+        config,
+        total_usd_fee,
+        fee_mint,
+        fee_account,
+        fee_source_account,
+        account_info_iter,
+    )?;
+    
+    let rebate_account = treasury_rebate_account(
+        program_id,
+        config,
+        dao_account,
+        &feature_fee,
+        payer,
+        system_program,
+        account_info_iter,
+    )?;
+    
+    msg!("Featured DAO fee: ${}.{:02} for {} days ({})", 
+        total_usd_fee / 100,
+        total_usd_fee % 100,
+        days,
+        feature_fee
+    );
+    Ok((feature_fee, rebate_account))
+}
+
+// Get the DAO treasury's account receiving the rebate of a fee. Stablecoin rebates go to the
+// treasury's associated token account, followed by the treasury and the associated token
+// program accounts so it can be created when missing
+fn treasury_rebate_account<'a, 'b, I: Iterator<Item = &'b AccountInfo<'a>>>(
+    program_id: &Pubkey,
+    config: &Config,
+    dao_account: &AccountInfo<'a>,
+    fee: &FeePayment<'a, 'b>,
+    payer: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    account_info_iter: &mut I,
+) -> Result<&'b AccountInfo<'a>, ProgramError> {
+    let (treasury, _) = pda::find_treasury_address(program_id, dao_account.key);
+    let rebate_account = next_account_info(account_info_iter)?;
+    fee.check_share_account(rebate_account, &treasury)?;
+    
+    if let FeePayment::Token { mint_account, token_program, .. } = fee {
+        let treasury_account = next_account_info(account_info_iter)?;
+        let associated_token_program = next_account_info(account_info_iter)?;
+        check_treasury(program_id, dao_account.key, treasury_account)?;
+        if config.fee_split.treasury_rebate_bps > 0 {
+            create_associated_token_account(
+                payer,
+                treasury_account,
+                rebate_account,
+                mint_account,
+                system_program,
+                token_program,
+                associated_token_program,
+            )?;
+        }
+    }
+    Ok(rebate_account)
+}
+
+// Platform fee priced in one of the accepted payment methods
+enum FeePayment<'a, 'b> {
+    // Lamports priced at the oracle SOL/USD price
+    Sol {
+        fee_account: &'b AccountInfo<'a>,
+        lamports: u64,
+        sol_price_usd: u64,
+    },
+    // Exact USD amount in an allowlisted stablecoin
+    Token {
+        source: &'b AccountInfo<'a>,
+        destination: &'b AccountInfo<'a>,
+        mint_account: &'b AccountInfo<'a>,
+        token_program: &'b AccountInfo<'a>,
+        amount: u64,
+    },
+}
+
+impl<'a, 'b> FeePayment<'a, 'b> {
+    // Price a fee in USD cents in SOL, or in `fee_mint` when the payer chose a stablecoin.
+    // The mint and token program accounts are only read for stablecoin payments.
+    fn new<I: Iterator<Item = &'b AccountInfo<'a>>>(
+        config: &Config,
+        fee_usd_cents: u64,
+        fee_mint: Option<Pubkey>,
+        fee_account: &'b AccountInfo<'a>,
+        source_account: &'b AccountInfo<'a>,
+        account_info_iter: &mut I,
+    ) -> Result<Self, ProgramError> {
+        let Some(fee_mint) = fee_mint else {
+            config.check_fee_account(fee_account)?;
+            let sol_price_usd =
+                oracle::sol_price_usd_cents(source_account, Clock::get()?.unix_timestamp)?;
+            return Ok(Self::Sol {
+                fee_account,
+                lamports: calculate_fee_in_lamports(fee_usd_cents, sol_price_usd)?,
+                sol_price_usd,
+            });
+        };
+        
+        let mint_account = next_account_info(account_info_iter)?;
+        let token_program = next_account_info(account_info_iter)?;
+        
+        // Only allowlisted stablecoins are accepted, at one USD per token
+        if !config.stable_mints.contains(&fee_mint)
+            || *mint_account.key != fee_mint
+            || *mint_account.owner != spl_token::id()
+        {
+            return Err(DaoError::InvalidFeeMint.into());
+        }
+        if *fee_account.key != get_associated_token_address(&config.fee_recipient, &fee_mint) {
+            return Err(DaoError::InvalidFeeAccount.into());
+        }
+        let mint = Mint::unpack(&mint_account.data.borrow())?;
+        
+        Ok(Self::Token {
+            source: source_account,
+            destination: fee_account,
+            mint_account,
+            token_program,
+            amount: calculate_fee_in_stable_tokens(fee_usd_cents, mint.decimals)?,
+        })
+    }
+    
+    // Lamports the payer needs on top of rent
+    fn lamports(&self) -> u64 {
+        match self {
+            Self::Sol { lamports, .. } => *lamports,
+            Self::Token { .. } => 0,
+        }
+    }
+    
+    // Verify an account can receive a share of the fee on behalf of `wallet`
+    fn check_share_account(&self, share_account: &AccountInfo, wallet: &Pubkey) -> ProgramResult {
+        let expected_share_pubkey = match self {
+            Self::Sol { .. } => *wallet,
+            Self::Token { mint_account, .. } => {
+                get_associated_token_address(wallet, mint_account.key)
+            }
+        };
+        if *share_account.key != expected_share_pubkey {
+            return Err(DaoError::InvalidFeeAccount.into());
+        }
+        Ok(())
+    }
+    
+    // Transfer the fee from the payer to the fee recipient, less the share of `share_bps`
+    // basis points sent to the share account, if any
+    fn pay(
+        &self,
+        payer: &AccountInfo<'a>,
+        system_program: &AccountInfo<'a>,
+        share: Option<(&AccountInfo<'a>, u16)>,
+    ) -> ProgramResult {
+        let (fee_amount, fee_account) = match self {
+            Self::Sol { lamports, fee_account, .. } => (*lamports, *fee_account),
+            Self::Token { amount, destination, .. } => (*amount, *destination),
+        };
+        let (platform_amount, share_amount) = match share {
+            Some((_, share_bps)) => split_fee(fee_amount, share_bps),
+            None => (fee_amount, 0),
+        };
+        
+        self.transfer(payer, fee_account, system_program, platform_amount)?;
+        if let Some((share_account, _)) = share {
+            if share_amount > 0 {
+                self.transfer(payer, share_account, system_program, share_amount)?;
+            }
+        }
+        Ok(())
+    }
+    
+    fn transfer(
+        &self,
+        payer: &AccountInfo<'a>,
+        destination: &AccountInfo<'a>,
+        system_program: &AccountInfo<'a>,
+        amount: u64,
+    ) -> ProgramResult {
+        match self {
+            Self::Sol { .. } => invoke(
+                &system_instruction::transfer(payer.key, destination.key, amount),
+                &[payer.clone(), destination.clone(), system_program.clone()],
+            ),
+            Self::Token { source, mint_account, token_program, .. } => {
+                transfer_tokens(
+                    source,
+                    destination,
+                    mint_account,
+                    payer,
+                    token_program,
+                    amount,
+                    &[],
+                )
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for FeePayment<'_, '_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Sol { lamports, sol_price_usd, .. } => write!(
+                f,
+                "{} lamports at SOL price of ${}.{:02}",
+                lamports,
+                sol_price_usd / 100,
+                sol_price_usd % 100
+            ),
+            Self::Token { mint_account, amount, .. } => {
+                write!(f, "{} tokens of mint {}", amount, mint_account.key)
+            }
+        }
+    }
+}
+
+// Vote weight of a voter: the balance of their associated token account for the governance mint
+fn token_vote_weight(
+    voter: &Pubkey,
+    mint: &Pubkey,
+    token_account: &AccountInfo,
+) -> Result<u64, ProgramError> {
+    if *token_account.key != get_associated_token_address(voter, mint) {
+        return Err(DaoError::InvalidTokenAccount.into());
+    }
+    if *token_account.owner != spl_token::id() {
+        return Err(DaoError::InvalidAccountOwner.into());
+    }
+    
+    let token_data = TokenAccount::unpack(&token_account.data.borrow())?;
+    if token_data.mint != *mint || token_data.owner != *voter {
+        return Err(DaoError::InvalidTokenAccount.into());
+    }
+    if token_data.amount == 0 {
+        return Err(DaoError::NoVotingPower.into());
+    }
+    
+    Ok(token_data.amount)
+}
+
+// Vote weight of a voter in reputation mode: their POL reputation score, capped to `max_weight`
+fn reputation_vote_weight(
+    program_id: &Pubkey,
+    module_account: &AccountInfo,
+    reputation_account: &AccountInfo,
+    dao_id: &Pubkey,
+    voter: &Pubkey,
+    now: i64,
+    max_weight: Option<u64>,
+) -> Result<u64, ProgramError> {
+    check_module_enabled(program_id, module_account, dao_id, ModuleType::Pol)?;
+    
+    let score = reputation_score(program_id, reputation_account, dao_id, voter, now)?;
+    if score == 0 {
+        return Err(DaoError::NoVotingPower.into());
+    }
+    
+    Ok(max_weight.map_or(score, |max_weight| score.min(max_weight)))
+}
+
+// Kind and options of a new proposal, from the option labels it was created with
+fn proposal_options(
+    labels: Vec<String>,
+    ranked: bool,
+) -> Result<(ProposalKind, Vec<ProposalOption>), ProgramError> {
+    if labels.is_empty() && !ranked {
+        let options = vec![
+            ProposalOption::new("For".to_string()),
+            ProposalOption::new("Against".to_string()),
+        ];
+        return Ok((ProposalKind::YesNo, options));
+    }
+    
+    if !(2..=MAX_PROPOSAL_OPTIONS).contains(&labels.len())
+        || labels.iter().any(|label| label.is_empty() || label.len() > MAX_OPTION_LABEL_LEN)
+        || labels.iter().enumerate().any(|(i, label)| labels[..i].contains(label))
+    {
+        return Err(DaoError::InvalidProposalOptions.into());
+    }
+    let kind = if ranked { ProposalKind::RankedChoice } else { ProposalKind::MultipleChoice };
+    Ok((kind, labels.into_iter().map(ProposalOption::new).collect()))
+}
+
+// Verify the instructions carried by a proposal can be safely invoked on behalf of the DAO
+fn check_proposal_instructions(
+    program_id: &Pubkey,
+    treasury: &Pubkey,
+    instructions: &[ProposalInstruction],
+) -> ProgramResult {
+    if instructions.len() > MAX_PROPOSAL_INSTRUCTIONS {
+        return Err(DaoError::InvalidProposalInstruction.into());
+    }
+    for instruction in instructions {
+        // Calling back into this program could overwrite the proposal being executed
+        if instruction.program_id == *program_id {
+            return Err(DaoError::InvalidProposalInstruction.into());
+        }
+        // The treasury is the only signer the program can provide
+        if instruction
+            .accounts
+            .iter()
+            .any(|meta| meta.is_signer && meta.pubkey != *treasury)
+        {
+            return Err(DaoError::InvalidProposalInstruction.into());
+        }
+    }
+    Ok(())
+}
+
+// Find an account by key among the accounts passed to the instruction
+fn find_account_info<'a, 'b>(
+    accounts: &'b [AccountInfo<'a>],
+    key: &Pubkey,
+) -> Result<&'b AccountInfo<'a>, ProgramError> {
+    accounts
+        .iter()
+        .find(|account| account.key == key)
+        .ok_or(ProgramError::NotEnoughAccountKeys)
+}
+
+// Verify an account is the DAO's treasury PDA, returning its bump seed
+fn check_treasury(
+    program_id: &Pubkey,
+    dao: &Pubkey,
+    treasury_account: &AccountInfo,
+) -> Result<u8, ProgramError> {
+    let (expected_treasury_pubkey, treasury_bump) = pda::find_treasury_address(program_id, dao);
+    if *treasury_account.key != expected_treasury_pubkey {
+        return Err(DaoError::InvalidAccountAddress.into());
+    }
+    Ok(treasury_bump)
+}
+
+// Move lamports out of an account owned by the program
+fn transfer_program_lamports(
+    source: &AccountInfo,
+    destination: &AccountInfo,
+    amount: u64,
+) -> ProgramResult {
+    let source_lamports = source
+        .lamports()
+        .checked_sub(amount)
+        .ok_or(DaoError::InsufficientFunds)?;
+    let destination_lamports = destination
+        .lamports()
+        .checked_add(amount)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    **source.try_borrow_mut_lamports()? = source_lamports;
+    **destination.try_borrow_mut_lamports()? = destination_lamports;
+    Ok(())
+}
+
+// Close an account owned by the program: move out all its lamports and wipe its data
+fn close_program_account(account: &AccountInfo, destination: &AccountInfo) -> ProgramResult {
+    transfer_program_lamports(account, destination, account.lamports())?;
+    account.data.borrow_mut().fill(0);
+    Ok(())
+}
+
+// Upgrade authority recorded in the program's ProgramData account, if it is still upgradeable
+fn program_upgrade_authority(
+    program_id: &Pubkey,
+    program_data_account: &AccountInfo,
+) -> Result<Option<Pubkey>, ProgramError> {
+    let (expected_program_data, _) =
+        Pubkey::find_program_address(&[program_id.as_ref()], &bpf_loader_upgradeable::id());
+    if *program_data_account.key != expected_program_data {
+        return Err(DaoError::InvalidAccountAddress.into());
+    }
+    if *program_data_account.owner != bpf_loader_upgradeable::id() {
+        return Err(DaoError::InvalidAccountOwner.into());
+    }
+    
+    // Bincode layout: u32 variant (3 = ProgramData), u64 slot, Option<Pubkey> authority
+    let data = program_data_account.data.borrow();
+    if data.len() < UpgradeableLoaderState::size_of_programdata_metadata()
+        || data[0..4] != 3u32.to_le_bytes()
+    {
+        return Err(DaoError::InvalidAccountType.into());
+    }
+    match data[12] {
+        0 => Ok(None),
+        1 => Ok(Some(Pubkey::try_from(&data[13..45]).unwrap())),
+        _ => Err(DaoError::InvalidAccountType.into()),
+    }
+}
+
+// Create a program-owned account at a PDA, signing for it with the given seeds
+fn create_pda_account<'a>(
+    payer: &AccountInfo<'a>,
+    new_account: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    program_id: &Pubkey,
+    lamports: u64,
+    space: u64,
+    signer_seeds: &[&[u8]],
+) -> ProgramResult {
+    if new_account.lamports() == 0 {
+        return invoke_signed(
+            &system_instruction::create_account(
+                payer.key,
+                new_account.key,
+                lamports,
+                space,
+                program_id,
+            ),
+            &[payer.clone(), new_account.clone(), system_program.clone()],
+            &[signer_seeds],
+        );
+    }
+
+    // Someone already sent lamports to the address, so create_account would fail:
+    // top it up to rent exemption and allocate/assign it instead
+    if new_account.owner != system_program.key || !new_account.data_is_empty() {
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+
+    let top_up = lamports.saturating_sub(new_account.lamports());
+    if top_up > 0 {
+        invoke(
+            &system_instruction::transfer(payer.key, new_account.key, top_up),
+            &[payer.clone(), new_account.clone(), system_program.clone()],
+        )?;
+    }
+    invoke_signed(
+        &system_instruction::allocate(new_account.key, space),
+        &[new_account.clone(), system_program.clone()],
+        &[signer_seeds],
+    )?;
+    invoke_signed(
+        &system_instruction::assign(new_account.key, program_id),
+        &[new_account.clone(), system_program.clone()],
+        &[signer_seeds],
+    )
+}
+
+// Process the create DAO instruction
+#[allow(clippy::too_many_arguments)]
+pub fn process_create_dao(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    name: String,
+    description: String,
+    discord_server: String,
+    twitter: String,
+    telegram: String,
+    instagram: String,
+    tiktok: String,
+    website: String,
+    profile: String,
+    token_address: String,
+    governance: GovernanceConfig,
+    fee_mint: Option<Pubkey>,
+    referrer: Option<Pubkey>,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    
+    // Get the required accounts
+    let creator_account = next_account_info(account_info_iter)?;
+    let dao_account = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    let fee_account = next_account_info(account_info_iter)?;
+    let treasury_account = next_account_info(account_info_iter)?;
+    let fee_source_account = next_account_info(account_info_iter)?;
+    let config_account = next_account_info(account_info_iter)?;
+    
+    // Verify the creator is signer
+    if !creator_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    
+    let config = load_config(program_id, config_account)?;
+    
+    // Verify the governance rules
+    governance.validate()?;
+    
+    // Verify the governance token address, if any, is a valid public key
+    if !token_address.is_empty() && Pubkey::from_str(&token_address).is_err() {
+        return Err(DaoError::InvalidTokenAddress.into());
+    }
+    
+    // Verify the DAO account is the expected PDA
+    let name_seed = pda::name_seed(&name);
+    let (expected_dao_pubkey, dao_bump) =
+        pda::find_dao_address(program_id, creator_account.key, &name);
+    if *dao_account.key != expected_dao_pubkey {
+        return Err(DaoError::InvalidAccountAddress.into());
+    }
+    
+    // Verify the treasury account is the DAO's treasury PDA
+    check_treasury(program_id, dao_account.key, treasury_account)?;
+    
+    // Calculate fee based on current SOL price, or in the chosen stablecoin
+    let create_dao_fee = FeePayment::new(
+        &config,
+        config.fees.create_dao,
+        fee_mint,
+        fee_account,
+        fee_source_account,
+        account_info_iter,
+    )?;
+    
+    // Get the referrer's account, which can't be the creator's own
+    let referrer_account = match referrer {
+        Some(referrer) => {
+            if referrer == *creator_account.key {
+                return Err(DaoError::InvalidReferrer.into());
+            }
+            let referrer_account = next_account_info(account_info_iter)?;
+            create_dao_fee.check_share_account(referrer_account, &referrer)?;
+            Some(referrer_account)
+        }
+        None => None,
+    };
+    
+    msg!("DAO creation fee: ${}.{:02} ({})", 
+        config.fees.create_dao / 100,
+        config.fees.create_dao % 100,
+        create_dao_fee
+    );
+    
+    // Create DAO data structure
+    let dao = Dao {
+        account_type: AccountType::Dao,
+        authority: *creator_account.key,
+        name,
+        description,
+        discord_server,
+        twitter,
+        telegram,
+        instagram,
+        tiktok,
+        website,
+        treasury: *treasury_account.key,
+        profile,
+        token_address,
+        governance,
+        module_manager: None,
+        allow_featured_sponsors: false,
+    };
+    
+    // Calculate space required for the DAO account
+    let dao_serialized = dao.try_to_vec()?;
+    let space = dao_serialized.len() as u64;
+    
+    // Calculate the rent required
+    let rent = Rent::get()?;
+    let rent_lamports = rent.minimum_balance(space as usize);
+    
+    // The treasury holds no data, it only needs to be rent exempt to exist
+    let treasury_lamports = rent.minimum_balance(0).saturating_sub(treasury_account.lamports());
+    
+    // Check if creator has enough funds for rent + fee
+    if creator_account.lamports() < rent_lamports + treasury_lamports + create_dao_fee.lamports() {
+        return Err(DaoError::InsufficientFunds.into());
+    }
+    
+    // Create the DAO account
+    create_pda_account(
+        creator_account,
+        dao_account,
+        system_program,
+        program_id,
+        rent_lamports,
+        space,
+        &[pda::DAO_SEED, creator_account.key.as_ref(), &name_seed, &[dao_bump]],
+    )?;
+    
+    // Fund the treasury account
+    if treasury_lamports > 0 {
+        invoke(
+            &system_instruction::transfer(
+                creator_account.key,
+                treasury_account.key,
+                treasury_lamports,
+            ),
+            &[creator_account.clone(), treasury_account.clone(), system_program.clone()],
+        )?;
+    }
+    
+    // Transfer fee to fee account
+    let referral = referrer_account.map(|account| (account, config.fee_split.referrer_bps));
+    create_dao_fee.pay(creator_account, system_program, referral)?;
+    
+    // Serialize and store the DAO data
+    dao.serialize(&mut &mut dao_account.data.borrow_mut()[..])?;
+    
+    msg!("DAO created successfully with ID: {}", dao_account.key);
+    Ok(())
+}
+
+// Process the create proposal instruction
+#[allow(clippy::too_many_arguments)]
+pub fn process_create_proposal(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    name: String,
+    description: String,
+    dao_id: Pubkey,
+    pod_id: String,
+    start_time: i64,
+    end_time: i64,
+    withdrawal: Option<TreasuryWithdrawal>,
+    mut instructions: Vec<ProposalInstruction>,
+    options: Vec<String>,
+    ranked: bool,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    
+    // Get the required accounts
+    let creator_account = next_account_info(account_info_iter)?;
+    let proposal_account = next_account_info(account_info_iter)?;
+    let dao_account = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    let config_account = next_account_info(account_info_iter)?;
+    
+    // Verify the creator is signer
+    if !creator_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    
+    // Get the pod and module registry accounts if the proposal is made for a pod
+    let pod_accounts = if pod_id.is_empty() {
+        None
+    } else {
+        Some((next_account_info(account_info_iter)?, next_account_info(account_info_iter)?))
+    };
+    
+    // Get the fee accounts if the platform charges for proposals
+    let config = load_config(program_id, config_account)?;
+    let fee_accounts = if config.fees.create_proposal > 0 {
+        let fee_account = next_account_info(account_info_iter)?;
+        config.check_fee_account(fee_account)?;
+        Some((fee_account, next_account_info(account_info_iter)?))
+    } else {
+        None
+    };
+    
+    // Verify the DAO exists and matches the given id
+    let dao = load_dao(program_id, dao_account, &dao_id)?;
+    
+    // Verify the pod is one of the DAO's, pods are still enabled and the creator belongs to it
+    if let Some((pod_account, module_account)) = pod_accounts {
+        if Pubkey::from_str(&pod_id).ok() != Some(*pod_account.key) {
+            return Err(DaoError::InvalidAccountAddress.into());
+        }
+        let pod: Pod = load_account(program_id, pod_account)?;
+        if pod.dao_id != dao_id {
+            return Err(DaoError::DaoMismatch.into());
+        }
+        check_module_enabled(program_id, module_account, &dao_id, ModuleType::Pod)?;
+        if !pod.is_member(creator_account.key) {
+            return Err(DaoError::NotPodMember.into());
+        }
+    }
+    
+    // Verify the treasury withdrawal, if any
+    if withdrawal.is_some_and(|withdrawal| withdrawal.amount == 0) {
+        return Err(DaoError::InvalidWithdrawal.into());
+    }
+    
+    // Verify the proposal instructions
+    let (treasury, _) = pda::find_treasury_address(program_id, dao_account.key);
+    check_proposal_instructions(program_id, &treasury, &instructions)?;
+    for instruction in instructions.iter_mut() {
+        instruction.executed = false;
+    }
+    
+    // Verify the options, which only proposals without actions can have. Quadratic
+    // ballots can't rank options, so ranked choice needs another voting mode
+    let (kind, options) = proposal_options(options, ranked)?;
+    let runoff = (kind == ProposalKind::RankedChoice).then(|| Runoff::new(options.len()));
+    if kind != ProposalKind::YesNo && (withdrawal.is_some() || !instructions.is_empty()) {
+        return Err(DaoError::InvalidProposalOptions.into());
+    }
+    if ranked && matches!(dao.governance.voting_mode, VotingMode::Quadratic { .. }) {
+        return Err(DaoError::InvalidProposalOptions.into());
+    }
+    
+    // Verify time constraints
+    let clock = Clock::get()?;
+    if start_time < clock.unix_timestamp || end_time <= start_time {
+        return Err(DaoError::ProposalTimeInvalid.into());
+    }
+    
+    // Calculate fee based on current SOL price
+    let proposal_fee = match fee_accounts {
+        Some((_, price_account)) => {
+            let sol_price_usd = oracle::sol_price_usd_cents(price_account, clock.unix_timestamp)?;
+            calculate_fee_in_lamports(config.fees.create_proposal, sol_price_usd)?
+        }
+        None => 0,
+    };
+    
+    // Verify the proposal account is the expected PDA
+    let name_seed = pda::name_seed(&name);
+    let (expected_proposal_pubkey, proposal_bump) =
+        pda::find_proposal_address(program_id, dao_account.key, creator_account.key, &name);
+    if *proposal_account.key != expected_proposal_pubkey {
+        return Err(DaoError::InvalidAccountAddress.into());
+    }
+    
+    // Create proposal data structure
+    let proposal = Proposal {
+        account_type: AccountType::Proposal,
+        authority: *creator_account.key,
+        name,
+        description,
+        dao_id,
+        pod_id,
+        start_time,
+        end_time,
+        kind,
+        options,
+        votes_abstain: 0,
+        weight_abstain: 0,
+        state: ProposalState::Voting,
+        finalized_at: 0,
+        executable_at: 0,
+        withdrawal,
+        instructions,
+        winning_option: None,
+        runoff,
+    };
+    
+    // Calculate space required for the proposal account
+    let space = proposal.space()? as u64;
+    
+    // Calculate the rent required
+    let rent = Rent::get()?;
+    let rent_lamports = rent.minimum_balance(space as usize);
+    
+    // Create the proposal account
+    create_pda_account(
+        creator_account,
+        proposal_account,
+        system_program,
+        program_id,
+        rent_lamports,
+        space,
+        &[
+            pda::PROPOSAL_SEED,
+            dao_account.key.as_ref(),
+            creator_account.key.as_ref(),
+            &name_seed,
+            &[proposal_bump],
+        ],
+    )?;
+    
+    // Transfer fee to fee account
+    if let Some((fee_account, _)) = fee_accounts {
+        msg!("Proposal fee: {} lamports", proposal_fee);
+        invoke(
+            &system_instruction::transfer(
+                creator_account.key,
+                fee_account.key,
+                proposal_fee,
+            ),
+            &[creator_account.clone(), fee_account.clone(), system_program.clone()],
+        )?;
+    }
+    
+    // Serialize and store the proposal data
+    proposal.serialize(&mut &mut proposal_account.data.borrow_mut()[..])?;
+    
+    msg!("Proposal created successfully with ID: {}", proposal_account.key);
+    Ok(())
+}
+
+// Process the vote instruction
+pub fn process_vote(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    choice: VoteChoice,
+    proposal_id: String,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    
+    // Get the required accounts
+    let voter_account = next_account_info(account_info_iter)?;
+    let vote_account = next_account_info(account_info_iter)?;
+    let proposal_account = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    let dao_account = next_account_info(account_info_iter)?;
+    let config_account = next_account_info(account_info_iter)?;
+    
+    // Verify the voter is signer
+    if !voter_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    
+    // Verify the proposal belongs to this program and matches the given id
+    let mut proposal: Proposal = load_account(program_id, proposal_account)?;
+    if Pubkey::from_str(&proposal_id).ok() != Some(*proposal_account.key) {
+        return Err(DaoError::ProposalMismatch.into());
+    }
+    let dao = load_dao(program_id, dao_account, &proposal.dao_id)?;
+    let config = load_config(program_id, config_account)?;
+    
+    // Get the accounts the vote weight is read from: the voter's token account if the
+    // DAO votes with a governance token, or the voter's reputation
+    let governance_mint = dao.governance_mint();
+    let weight_accounts = match (dao.governance.voting_mode, governance_mint) {
+        (VotingMode::Token | VotingMode::Quadratic { .. }, None) => Vec::new(),
+        (VotingMode::Token | VotingMode::Quadratic { .. }, Some(_)) => {
+            vec![next_account_info(account_info_iter)?]
+        }
+        (VotingMode::Reputation { .. }, _) => vec![
+            next_account_info(account_info_iter)?,
+            next_account_info(account_info_iter)?,
+        ],
+    };
+    
+    // Get the accounts escrowing the voter's governance tokens
+    let escrow_accounts = match (dao.governance.voting_mode, governance_mint) {
+        (VotingMode::Token | VotingMode::Quadratic { .. }, Some(_)) => Some((
+            next_account_info(account_info_iter)?,
+            next_account_info(account_info_iter)?,
+            next_account_info(account_info_iter)?,
+            next_account_info(account_info_iter)?,
+        )),
+        _ => None,
+    };
+    
+    // Get the fee accounts if the platform charges for votes
+    let fee_accounts = if config.fees.vote > 0 {
+        let fee_account = next_account_info(account_info_iter)?;
+        config.check_fee_account(fee_account)?;
+        Some((fee_account, next_account_info(account_info_iter)?))
+    } else {
+        None
+    };
+    
+    // Verify the vote account is the expected PDA
+    let (expected_vote_pubkey, vote_bump) =
+        pda::find_vote_address(program_id, proposal_account.key, voter_account.key);
+    if *vote_account.key != expected_vote_pubkey {
+        return Err(DaoError::InvalidAccountAddress.into());
+    }
+    
+    // A vote record already exists for this (proposal, voter) pair
+    if vote_account.owner == program_id || !vote_account.data_is_empty() {
+        msg!("Voter {} already voted on proposal {}", voter_account.key, proposal_account.key);
+        return Err(DaoError::AlreadyVoted.into());
+    }
+    
+    // Verify the proposal is open for voting
+    let clock = Clock::get()?;
+    proposal.check_voting_window(clock.unix_timestamp)?;
+    
+    // Calculate fee based on current SOL price
+    let vote_fee = match fee_accounts {
+        Some((_, price_account)) => {
+            let sol_price_usd = oracle::sol_price_usd_cents(price_account, clock.unix_timestamp)?;
+            calculate_fee_in_lamports(config.fees.vote, sol_price_usd)?
+        }
+        None => 0,
+    };
+    
+    // Weigh the vote by the voter's governance token balance or reputation. Token balances
+    // are escrowed with the vote, so they can't vote again from another wallet
+    let mut locked_amount = 0;
+    let weight = match (dao.governance.voting_mode, governance_mint, &weight_accounts[..]) {
+        (VotingMode::Token, Some(mint), [token_account]) => {
+            locked_amount = token_vote_weight(voter_account.key, &mint, token_account)?;
+            locked_amount
+        }
+        (VotingMode::Reputation { max_weight }, _, [module_account, reputation_account]) => {
+            reputation_vote_weight(
+                program_id,
+                module_account,
+                reputation_account,
+                &proposal.dao_id,
+                voter_account.key,
+                clock.unix_timestamp,
+                max_weight,
+            )?
+        }
+        (VotingMode::Quadratic { voice_credits }, mint, token_accounts) => {
+            // Every token holder gets the same budget, whatever their balance
+            if let (Some(mint), [token_account]) = (mint, token_accounts) {
+                locked_amount = token_vote_weight(voter_account.key, &mint, token_account)?;
+            }
+            voice_credits
+        }
+        _ => 1,
+    };
+    
+    // Update the proposal tallies. Quadratic ballots spend the voter's voice credits,
+    // and quadratic abstentions weigh as much as the whole budget spent on one option
+    let (weight, credits_spent) = match (&choice, dao.governance.voting_mode) {
+        (VoteChoice::Credits(allocations), VotingMode::Quadratic { .. }) => {
+            proposal.record_vote(&choice, weight)?;
+            let weight = allocations.iter().map(CreditAllocation::weight).sum();
+            (weight, choice.voice_credits()?)
+        }
+        (VoteChoice::Abstain, VotingMode::Quadratic { .. }) => {
+            proposal.record_vote(&choice, isqrt(weight))?;
+            (isqrt(weight), 0)
+        }
+        (VoteChoice::Credits(_), _) | (_, VotingMode::Quadratic { .. }) => {
+            return Err(DaoError::InvalidVote.into());
+        }
+        _ => {
+            proposal.record_vote(&choice, weight)?;
+            (weight, 0)
+        }
+    };
+    
+    // Create vote data structure, ranked ballots being counted in the first runoff round
+    let counted_round = match choice {
+        VoteChoice::Ranking(_) => 1,
+        _ => 0,
+    };
+    let vote_data = Vote {
+        account_type: AccountType::Vote,
+        voter: *voter_account.key,
+        choice,
+        proposal_id,
+        weight,
+        counted_round,
+        credits_spent,
+        locked_amount,
+        locked_mint: if locked_amount > 0 { governance_mint } else { None },
+    };
+    
+    // Calculate space required for the vote account
+    let vote_serialized = vote_data.try_to_vec()?;
+    let space = vote_serialized.len() as u64;
+    
+    // Calculate the rent required
+    let rent = Rent::get()?;
+    let rent_lamports = rent.minimum_balance(space as usize);
+    
+    // Create the vote account
+    create_pda_account(
+        voter_account,
+        vote_account,
+        system_program,
+        program_id,
+        rent_lamports,
+        space,
+        &[
+            pda::VOTE_SEED,
+            proposal_account.key.as_ref(),
+            voter_account.key.as_ref(),
+            &[vote_bump],
+        ],
+    )?;
+    
+    // Transfer fee to fee account
+    if let Some((fee_account, _)) = fee_accounts {
+        msg!("Vote fee: {} lamports", vote_fee);
+        invoke(
+            &system_instruction::transfer(
+                voter_account.key,
+                fee_account.key,
+                vote_fee,
+            ),
+            &[voter_account.clone(), fee_account.clone(), system_program.clone()],
+        )?;
+    }
+    
+    // Escrow the voter's governance tokens
+    if let (Some((escrow_account, mint_account, token_program, ata_program)), [token_account]) =
+        (escrow_accounts, &weight_accounts[..])
+    {
+        if Some(*mint_account.key) != governance_mint {
+            return Err(DaoError::InvalidTokenAccount.into());
+        }
+        lock_vote_tokens(
+            voter_account,
+            token_account,
+            vote_account,
+            escrow_account,
+            mint_account,
+            system_program,
+            token_program,
+            ata_program,
+            locked_amount,
+        )?;
+    }
+    
+    // Serialize and store the vote data
+    vote_data.serialize(&mut &mut vote_account.data.borrow_mut()[..])?;
+    
+    // Store the updated tallies
+    proposal.serialize(&mut &mut proposal_account.data.borrow_mut()[..])?;
+    
+    msg!("Vote recorded successfully with weight {} for {:?}", weight, vote_data.choice);
+    Ok(())
 }
 
 // Process the featured instruction
 pub fn process_featured(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
-    dao_id: String,
-    days: u64,
-    sol_price_usd: u64,
+    dao_id: Pubkey,
+    days: u64,
+    fee_mint: Option<Pubkey>,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    
+    // Get the required accounts
+    let creator_account = next_account_info(account_info_iter)?;
+    let featured_account = next_account_info(account_info_iter)?;
+    let dao_account = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    let fee_account = next_account_info(account_info_iter)?;
+    let fee_source_account = next_account_info(account_info_iter)?;
+    let config_account = next_account_info(account_info_iter)?;
+    
+    // Verify the creator is signer
+    if !creator_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    
+    let config = load_config(program_id, config_account)?;
+    
+    // Featured slots are only sold by auction when auctions are enabled
+    if config.featured_auction.slots > 0 {
+        return Err(DaoError::FeaturedSlotsAuctioned.into());
+    }
+    
+    // Verify the DAO exists and the creator may feature it
+    let dao = load_dao(program_id, dao_account, &dao_id)?;
+    dao.check_featured_sponsor(creator_account.key)?;
+    
+    // Verify the featured account is the expected PDA
+    let (expected_featured_pubkey, featured_bump) =
+        pda::find_featured_address(program_id, dao_account.key);
+    if *featured_account.key != expected_featured_pubkey {
+        return Err(DaoError::InvalidAccountAddress.into());
+    }
+    
+    // Calculate fee based on number of days and current SOL price, or in the chosen stablecoin
+    let (feature_fee, rebate_account) = featured_fee(
+        program_id,
+        &config,
+        dao_account,
+        days,
+        fee_mint,
+        creator_account,
+        system_program,
+        fee_account,
+        fee_source_account,
+        account_info_iter,
+    )?;
+    
+    // Create featured data structure, featuring the DAO from now on
+    let mut featured_data = Featured {
+        account_type: AccountType::Featured,
+        authority: *creator_account.key,
+        dao_id,
+        days: 0,
+        starts_at: 0,
+        expires_at: 0,
+    };
+    featured_data.extend(Clock::get()?.unix_timestamp, days)?;
+    
+    // Calculate space required for the featured account
+    let featured_serialized = featured_data.try_to_vec()?;
+    let space = featured_serialized.len() as u64;
+    
+    // Calculate the rent required
+    let rent = Rent::get()?;
+    let rent_lamports = rent.minimum_balance(space as usize);
+    
+    // Check if creator has enough funds for rent + fee
+    if creator_account.lamports() < rent_lamports + feature_fee.lamports() {
+        return Err(DaoError::InsufficientFunds.into());
+    }
+    
+    // Create the featured account
+    create_pda_account(
+        creator_account,
+        featured_account,
+        system_program,
+        program_id,
+        rent_lamports,
+        space,
+        &[pda::FEATURED_SEED, dao_account.key.as_ref(), &[featured_bump]],
+    )?;
+    
+    // Transfer fee to fee account
+    let rebate = (rebate_account, config.fee_split.treasury_rebate_bps);
+    feature_fee.pay(creator_account, system_program, Some(rebate))?;
+    
+    // Serialize and store the featured data
+    featured_data.serialize(&mut &mut featured_account.data.borrow_mut()[..])?;
+    
+    msg!(
+        "DAO featured successfully with ID: {} until {}",
+        featured_account.key,
+        featured_data.expires_at
+    );
+    Ok(())
+}
+
+// Process the renew featured instruction
+pub fn process_renew_featured(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    dao_id: Pubkey,
+    days: u64,
+    fee_mint: Option<Pubkey>,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    
+    // Get the required accounts
+    let payer_account = next_account_info(account_info_iter)?;
+    let featured_account = next_account_info(account_info_iter)?;
+    let dao_account = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    let fee_account = next_account_info(account_info_iter)?;
+    let fee_source_account = next_account_info(account_info_iter)?;
+    let config_account = next_account_info(account_info_iter)?;
+    
+    // Verify the payer is signer
+    if !payer_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    
+    let config = load_config(program_id, config_account)?;
+    
+    // Featured slots are only sold by auction when auctions are enabled
+    if config.featured_auction.slots > 0 {
+        return Err(DaoError::FeaturedSlotsAuctioned.into());
+    }
+    
+    // Verify the DAO exists, the payer may feature it and the listing is the DAO's
+    let dao = load_dao(program_id, dao_account, &dao_id)?;
+    dao.check_featured_sponsor(payer_account.key)?;
+    let mut featured_data: Featured = load_account(program_id, featured_account)?;
+    if featured_data.dao_id != dao_id {
+        return Err(DaoError::DaoMismatch.into());
+    }
+    
+    // Calculate fee based on number of days and current SOL price, or in the chosen stablecoin
+    let (feature_fee, rebate_account) = featured_fee(
+        program_id,
+        &config,
+        dao_account,
+        days,
+        fee_mint,
+        payer_account,
+        system_program,
+        fee_account,
+        fee_source_account,
+        account_info_iter,
+    )?;
+    
+    featured_data.extend(Clock::get()?.unix_timestamp, days)?;
+    
+    // Transfer fee to fee account
+    let rebate = (rebate_account, config.fee_split.treasury_rebate_bps);
+    feature_fee.pay(payer_account, system_program, Some(rebate))?;
+    
+    featured_data.serialize(&mut &mut featured_account.data.borrow_mut()[..])?;
+    
+    msg!("DAO {} featured until {}", dao_id, featured_data.expires_at);
+    Ok(())
+}
+
+// Process the close expired featured instruction
+pub fn process_close_expired_featured(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    
+    // Get the required accounts
+    let featured_account = next_account_info(account_info_iter)?;
+    let authority_account = next_account_info(account_info_iter)?;
+    
+    // Rent goes back to whoever created the listing
+    let featured_data: Featured = load_account(program_id, featured_account)?;
+    if *authority_account.key != featured_data.authority {
+        return Err(DaoError::Unauthorized.into());
+    }
+    
+    let clock = Clock::get()?;
+    if clock.unix_timestamp < featured_data.expires_at {
+        return Err(DaoError::FeaturedNotExpired.into());
+    }
+    
+    close_program_account(featured_account, authority_account)?;
+    
+    msg!("Expired featured listing of DAO {} closed", featured_data.dao_id);
+    Ok(())
+}
+
+// Process the place featured bid instruction
+pub fn process_place_featured_bid(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    dao_id: Pubkey,
+    period: u64,
+    amount: u64,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    
+    // Get the required accounts
+    let bidder_account = next_account_info(account_info_iter)?;
+    let auction_account = next_account_info(account_info_iter)?;
+    let bid_account = next_account_info(account_info_iter)?;
+    let dao_account = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    let config_account = next_account_info(account_info_iter)?;
+    let price_account = next_account_info(account_info_iter)?;
+    
+    // Verify the bidder is signer
+    if !bidder_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    if amount == 0 {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    
+    let config = load_config(program_id, config_account)?;
+    let dao = load_dao(program_id, dao_account, &dao_id)?;
+    dao.check_featured_sponsor(bidder_account.key)?;
+    let clock = Clock::get()?;
+    
+    // Load the auction, or open it with the first bid of the period
+    let (expected_auction_pubkey, auction_bump) = pda::find_auction_address(program_id, period);
+    if *auction_account.key != expected_auction_pubkey {
+        return Err(DaoError::InvalidAccountAddress.into());
+    }
+    let new_auction = auction_account.owner != program_id;
+    let mut auction = if new_auction {
+        FeaturedAuction::open(
+            &config.featured_auction,
+            period,
+            *bidder_account.key,
+            clock.unix_timestamp,
+        )?
+    } else {
+        load_account::<FeaturedAuction>(program_id, auction_account)?
+    };
+    if auction.settled || clock.unix_timestamp >= auction.starts_at {
+        return Err(DaoError::AuctionClosed.into());
+    }
+    
+    // Load the DAO's bid, or place its first one
+    let (expected_bid_pubkey, bid_bump) =
+        pda::find_bid_address(program_id, auction_account.key, dao_account.key);
+    if *bid_account.key != expected_bid_pubkey {
+        return Err(DaoError::InvalidAccountAddress.into());
+    }
+    let new_bid = bid_account.owner != program_id;
+    let mut bid = if new_bid {
+        FeaturedBid {
+            account_type: AccountType::FeaturedBid,
+            auction: *auction_account.key,
+            dao_id,
+            bidder: *bidder_account.key,
+            amount: 0,
+        }
+    } else {
+        load_account::<FeaturedBid>(program_id, bid_account)?
+    };
+    if bid.bidder != *bidder_account.key {
+        return Err(DaoError::Unauthorized.into());
+    }
+    bid.amount = bid.amount.checked_add(amount).ok_or(ProgramError::ArithmeticOverflow)?;
+    
+    // The bid must be worth at least the featured fee for the period
+    let sol_price_usd = oracle::sol_price_usd_cents(price_account, clock.unix_timestamp)?;
+    let reserve_usd = calculate_featured_fee_usd_cents(
+        config.fees.featured_per_day,
+        &config.featured_tiers,
+        auction.days,
+    )?;
+    let reserve = calculate_fee_in_lamports(reserve_usd, sol_price_usd)?;
+    if bid.amount < reserve {
+        msg!("Bid of {} lamports is below the reserve of {} lamports", bid.amount, reserve);
+        return Err(DaoError::BidTooLow.into());
+    }
+    
+    // The bid must win one of the slots
+    auction.place_bid(dao_id, bid.amount)?;
+    
+    let rent = Rent::get()?;
+    if new_auction {
+        let space = auction.max_space();
+        create_pda_account(
+            bidder_account,
+            auction_account,
+            system_program,
+            program_id,
+            rent.minimum_balance(space),
+            space as u64,
+            &[pda::AUCTION_SEED, &period.to_le_bytes(), &[auction_bump]],
+        )?;
+    }
+    
+    // Escrow the lamports in the bid account
+    if new_bid {
+        auction.open_bids =
+            auction.open_bids.checked_add(1).ok_or(ProgramError::ArithmeticOverflow)?;
+        let space = bid.try_to_vec()?.len();
+        create_pda_account(
+            bidder_account,
+            bid_account,
+            system_program,
+            program_id,
+            rent.minimum_balance(space)
+                .checked_add(amount)
+                .ok_or(ProgramError::ArithmeticOverflow)?,
+            space as u64,
+            &[
+                pda::BID_SEED,
+                auction_account.key.as_ref(),
+                dao_account.key.as_ref(),
+                &[bid_bump],
+            ],
+        )?;
+    } else {
+        invoke(
+            &system_instruction::transfer(bidder_account.key, bid_account.key, amount),
+            &[bidder_account.clone(), bid_account.clone(), system_program.clone()],
+        )?;
+    }
+    
+    bid.serialize(&mut &mut bid_account.data.borrow_mut()[..])?;
+    auction.serialize(&mut &mut auction_account.data.borrow_mut()[..])?;
+    
+    msg!("DAO {} bid {} lamports for featured period {}", dao_id, bid.amount, period);
+    Ok(())
+}
+
+// Process the settle featured auction instruction
+pub fn process_settle_featured_auction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    
+    // Get the required accounts
+    let auction_account = next_account_info(account_info_iter)?;
+    
+    let mut auction: FeaturedAuction = load_account(program_id, auction_account)?;
+    if auction.settled {
+        return Err(DaoError::AuctionClosed.into());
+    }
+    
+    // Bidding closes when the auctioned period starts
+    let clock = Clock::get()?;
+    if clock.unix_timestamp < auction.starts_at {
+        return Err(DaoError::AuctionNotEnded.into());
+    }
+    
+    auction.settled = true;
+    auction.serialize(&mut &mut auction_account.data.borrow_mut()[..])?;
+    
+    for (rank, entry) in auction.leaders.iter().enumerate() {
+        msg!("Featured slot {}: DAO {} with {} lamports", rank + 1, entry.dao_id, entry.amount);
+    }
+    Ok(())
+}
+
+// Process the claim featured slot instruction
+pub fn process_claim_featured_slot(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    
+    // Get the required accounts
+    let bidder_account = next_account_info(account_info_iter)?;
+    let auction_account = next_account_info(account_info_iter)?;
+    let bid_account = next_account_info(account_info_iter)?;
+    let featured_account = next_account_info(account_info_iter)?;
+    let dao_account = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    let config_account = next_account_info(account_info_iter)?;
+    let fee_account = next_account_info(account_info_iter)?;
+    let treasury_account = next_account_info(account_info_iter)?;
+    
+    // Verify the bidder is signer
+    if !bidder_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    
+    // Verify the bid won a slot of the settled auction
+    let mut auction: FeaturedAuction = load_account(program_id, auction_account)?;
+    let bid: FeaturedBid = load_account(program_id, bid_account)?;
+    if bid.auction != *auction_account.key {
+        return Err(DaoError::InvalidAccountAddress.into());
+    }
+    if bid.bidder != *bidder_account.key {
+        return Err(DaoError::Unauthorized.into());
+    }
+    if !auction.settled {
+        return Err(DaoError::AuctionNotSettled.into());
+    }
+    if !auction.is_winning(&bid.dao_id) {
+        return Err(DaoError::BidNotWinning.into());
+    }
+    
+    // Bids aren't charged for periods that are already over
+    let now = Clock::get()?.unix_timestamp;
+    if auction.ends_at <= now {
+        return Err(DaoError::FeaturedPeriodEnded.into());
+    }
+    
+    // Verify the DAO, its treasury and the fee recipient
+    load_dao(program_id, dao_account, &bid.dao_id)?;
+    check_treasury(program_id, dao_account.key, treasury_account)?;
+    let config = load_config(program_id, config_account)?;
+    config.check_fee_account(fee_account)?;
+    
+    // Verify the featured account is the expected PDA
+    let (expected_featured_pubkey, featured_bump) =
+        pda::find_featured_address(program_id, dao_account.key);
+    if *featured_account.key != expected_featured_pubkey {
+        return Err(DaoError::InvalidAccountAddress.into());
+    }
+    
+    // Feature the DAO for the auctioned period
+    let featured_data = if featured_account.owner == program_id {
+        let mut featured_data: Featured = load_account(program_id, featured_account)?;
+        featured_data.schedule(now, auction.starts_at, auction.ends_at, auction.days)?;
+        featured_data
+    } else {
+        let featured_data = Featured {
+            account_type: AccountType::Featured,
+            authority: *bidder_account.key,
+            dao_id: bid.dao_id,
+            days: auction.days,
+            starts_at: auction.starts_at,
+            expires_at: auction.ends_at,
+        };
+        let space = featured_data.try_to_vec()?.len();
+        create_pda_account(
+            bidder_account,
+            featured_account,
+            system_program,
+            program_id,
+            Rent::get()?.minimum_balance(space),
+            space as u64,
+            &[pda::FEATURED_SEED, dao_account.key.as_ref(), &[featured_bump]],
+        )?;
+        featured_data
+    };
+    featured_data.serialize(&mut &mut featured_account.data.borrow_mut()[..])?;
+    
+    // Pay out the escrowed bid, then return the bid account's rent to the bidder
+    let (platform_amount, rebate_amount) =
+        split_fee(bid.amount, config.fee_split.treasury_rebate_bps);
+    transfer_program_lamports(bid_account, fee_account, platform_amount)?;
+    transfer_program_lamports(bid_account, treasury_account, rebate_amount)?;
+    close_program_account(bid_account, bidder_account)?;
+    auction.close_bid()?;
+    auction.serialize(&mut &mut auction_account.data.borrow_mut()[..])?;
+    
+    msg!(
+        "DAO {} featured from {} to {} for {} lamports",
+        bid.dao_id,
+        auction.starts_at,
+        auction.ends_at,
+        bid.amount
+    );
+    Ok(())
+}
+
+// Process the refund featured bid instruction
+pub fn process_refund_featured_bid(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    
+    // Get the required accounts
+    let bidder_account = next_account_info(account_info_iter)?;
+    let auction_account = next_account_info(account_info_iter)?;
+    let bid_account = next_account_info(account_info_iter)?;
+    
+    let mut auction: FeaturedAuction = load_account(program_id, auction_account)?;
+    let bid: FeaturedBid = load_account(program_id, bid_account)?;
+    if bid.auction != *auction_account.key {
+        return Err(DaoError::InvalidAccountAddress.into());
+    }
+    if bid.bidder != *bidder_account.key {
+        return Err(DaoError::Unauthorized.into());
+    }
+    
+    // Bids holding a slot stay escrowed until they are claimed, or the period ends unclaimed
+    if auction.is_winning(&bid.dao_id) && Clock::get()?.unix_timestamp < auction.ends_at {
+        return Err(DaoError::BidNotRefundable.into());
+    }
+    
+    close_program_account(bid_account, bidder_account)?;
+    auction.close_bid()?;
+    auction.serialize(&mut &mut auction_account.data.borrow_mut()[..])?;
+    
+    msg!("Refunded the bid of DAO {} ({} lamports)", bid.dao_id, bid.amount);
+    Ok(())
+}
+
+// Process the close featured auction instruction
+pub fn process_close_featured_auction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    
+    // Get the required accounts
+    let auction_account = next_account_info(account_info_iter)?;
+    let opener_account = next_account_info(account_info_iter)?;
+    
+    // Rent goes back to whoever opened the auction
+    let auction: FeaturedAuction = load_account(program_id, auction_account)?;
+    if *opener_account.key != auction.opener {
+        return Err(DaoError::Unauthorized.into());
+    }
+    
+    // Every bid must be paid out or refunded first
+    if !auction.settled {
+        return Err(DaoError::AuctionNotSettled.into());
+    }
+    if auction.open_bids > 0 {
+        return Err(DaoError::AuctionBidsOutstanding.into());
+    }
+    
+    close_program_account(auction_account, opener_account)?;
+    
+    msg!("Featured auction of period {} closed", auction.period);
+    Ok(())
+}
+
+// Process the release vote tokens instruction
+pub fn process_release_vote_tokens(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    
+    // Get the required accounts
+    let voter_account = next_account_info(account_info_iter)?;
+    let vote_account = next_account_info(account_info_iter)?;
+    let proposal_account = next_account_info(account_info_iter)?;
+    let escrow_account = next_account_info(account_info_iter)?;
+    let voter_token_account = next_account_info(account_info_iter)?;
+    let mint_account = next_account_info(account_info_iter)?;
+    let token_program = next_account_info(account_info_iter)?;
+    
+    // Verify the vote is the voter's vote on the proposal
+    let proposal: Proposal = load_account(program_id, proposal_account)?;
+    let (expected_vote_pubkey, vote_bump) =
+        pda::find_vote_address(program_id, proposal_account.key, voter_account.key);
+    if *vote_account.key != expected_vote_pubkey {
+        return Err(DaoError::InvalidAccountAddress.into());
+    }
+    let mut vote: Vote = load_account(program_id, vote_account)?;
+    
+    // Tokens stay locked as long as the proposal can still be decided
+    if matches!(proposal.state, ProposalState::Voting | ProposalState::Tallying) {
+        return Err(DaoError::ProposalNotFinalized.into());
+    }
+    if vote.locked_amount == 0 {
+        return Err(DaoError::NoLockedTokens.into());
+    }
+    
+    // Verify the escrow and destination token accounts hold the tokens locked by the vote
+    if *token_program.key != spl_token::id() {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    if Some(*mint_account.key) != vote.locked_mint
+        || *escrow_account.key != get_associated_token_address(vote_account.key, mint_account.key)
+        || *voter_token_account.key
+            != get_associated_token_address(voter_account.key, mint_account.key)
+    {
+        return Err(DaoError::InvalidTokenAccount.into());
+    }
+    
+    // Return the tokens and close the escrow, signing as the vote account
+    let vote_seeds: &[&[u8]] = &[
+        pda::VOTE_SEED,
+        proposal_account.key.as_ref(),
+        voter_account.key.as_ref(),
+        &[vote_bump],
+    ];
+    transfer_tokens(
+        escrow_account,
+        voter_token_account,
+        mint_account,
+        vote_account,
+        token_program,
+        vote.locked_amount,
+        &[vote_seeds],
+    )?;
+    invoke_signed(
+        &spl_token::instruction::close_account(
+            token_program.key,
+            escrow_account.key,
+            voter_account.key,
+            vote_account.key,
+            &[],
+        )?,
+        &[
+            escrow_account.clone(),
+            voter_account.clone(),
+            vote_account.clone(),
+            token_program.clone(),
+        ],
+        &[vote_seeds],
+    )?;
+    
+    msg!("Released {} tokens to {}", vote.locked_amount, voter_account.key);
+    vote.locked_amount = 0;
+    vote.serialize(&mut &mut vote_account.data.borrow_mut()[..])?;
+    Ok(())
+}
+
+// Process the modules instruction
+pub fn process_modules(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    dao_id: Pubkey,
+    module_type: ModuleType,
+    fee_mint: Option<Pubkey>,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    
+    // Get the required accounts
+    let creator_account = next_account_info(account_info_iter)?;
+    let module_account = next_account_info(account_info_iter)?;
+    let dao_account = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    let fee_account = next_account_info(account_info_iter)?;
+    let fee_source_account = next_account_info(account_info_iter)?;
+    let config_account = next_account_info(account_info_iter)?;
+    
+    // Verify the creator is signer
+    if !creator_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    
+    let config = load_config(program_id, config_account)?;
+    
+    // Verify the DAO exists and the creator may manage its modules
+    let dao = load_dao(program_id, dao_account, &dao_id)?;
+    dao.check_module_manager(creator_account.key)?;
+    
+    // Verify the module registry is the expected PDA
+    let (expected_module_pubkey, module_bump) =
+        pda::find_module_address(program_id, dao_account.key);
+    if *module_account.key != expected_module_pubkey {
+        return Err(DaoError::InvalidAccountAddress.into());
+    }
+    
+    // Load the DAO's registry, or start a new one on first activation
+    let new_registry = module_account.owner != program_id;
+    let mut registry = if new_registry {
+        ModuleRegistry {
+            account_type: AccountType::Module,
+            dao_id,
+            pod_enabled: false,
+            pol_enabled: false,
+        }
+    } else {
+        load_account(program_id, module_account)?
+    };
+    registry.enable(module_type)?;
+    let module_fee_usd = config.fees.module_fee(module_type);
+    
+    // Calculate fee based on current SOL price, or in the chosen stablecoin
+    let module_fee = FeePayment::new(
+        &config,
+        module_fee_usd,
+        fee_mint,
+        fee_account,
+        fee_source_account,
+        account_info_iter,
+    )?;
+    
+    // Get the DAO treasury's account receiving the rebate
+    let rebate_account = treasury_rebate_account(
+        program_id,
+        &config,
+        dao_account,
+        &module_fee,
+        creator_account,
+        system_program,
+        account_info_iter,
+    )?;
+    
+    msg!("Module activation fee: ${}.{:02} ({})", 
+        module_fee_usd / 100,
+        module_fee_usd % 100,
+        module_fee
+    );
+    
+    // The registry has a fixed size, so it is only paid for on first activation
+    let space = registry.try_to_vec()?.len();
+    let rent_lamports = if new_registry {
+        Rent::get()?.minimum_balance(space)
+    } else {
+        0
+    };
+    
+    // Check if creator has enough funds for rent + fee
+    if creator_account.lamports() < rent_lamports + module_fee.lamports() {
+        return Err(DaoError::InsufficientFunds.into());
+    }
+    
+    // Create the module registry
+    if new_registry {
+        create_pda_account(
+            creator_account,
+            module_account,
+            system_program,
+            program_id,
+            rent_lamports,
+            space as u64,
+            &[pda::MODULE_SEED, dao_account.key.as_ref(), &[module_bump]],
+        )?;
+    }
+    
+    // Transfer fee to fee account
+    let rebate = (rebate_account, config.fee_split.treasury_rebate_bps);
+    module_fee.pay(creator_account, system_program, Some(rebate))?;
+    
+    // Serialize and store the module registry
+    registry.serialize(&mut &mut module_account.data.borrow_mut()[..])?;
+    
+    msg!("DAO module {:?} activated in registry {}", module_type, module_account.key);
+    Ok(())
+}
+
+// Process the disable module instruction
+pub fn process_disable_module(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    dao_id: Pubkey,
+    module_type: ModuleType,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    
+    // Get the required accounts
+    let manager_account = next_account_info(account_info_iter)?;
+    let module_account = next_account_info(account_info_iter)?;
+    let dao_account = next_account_info(account_info_iter)?;
+    
+    // Verify the manager is signer
+    if !manager_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    
+    // Verify the DAO exists and the signer may manage its modules
+    let dao = load_dao(program_id, dao_account, &dao_id)?;
+    dao.check_module_manager(manager_account.key)?;
+    
+    // Verify the module registry is the DAO's
+    let (expected_module_pubkey, _) = pda::find_module_address(program_id, dao_account.key);
+    if *module_account.key != expected_module_pubkey {
+        return Err(DaoError::InvalidAccountAddress.into());
+    }
+    let mut registry: ModuleRegistry = load_account(program_id, module_account)?;
+    registry.disable(module_type)?;
+    registry.serialize(&mut &mut module_account.data.borrow_mut()[..])?;
+    
+    msg!("DAO module {:?} disabled in registry {}", module_type, module_account.key);
+    Ok(())
+}
+
+// Process the finalize proposal instruction
+pub fn process_finalize_proposal(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    
+    // Get the required accounts
+    let proposal_account = next_account_info(account_info_iter)?;
+    let dao_account = next_account_info(account_info_iter)?;
+    
+    // Load the proposal and the DAO it belongs to
+    let mut proposal: Proposal = load_account(program_id, proposal_account)?;
+    let dao = load_dao(program_id, dao_account, &proposal.dao_id)?;
+    
+    if proposal.state != ProposalState::Voting {
+        return Err(DaoError::ProposalAlreadyFinalized.into());
+    }
+    
+    // Verify the voting period is over
+    let clock = Clock::get()?;
+    if clock.unix_timestamp <= proposal.end_time {
+        return Err(DaoError::VotingNotEnded.into());
+    }
+    
+    // Evaluate the tallies against the DAO's governance rules, queueing
+    // passed proposals until the execution delay elapses. Ranked choice proposals
+    // reaching the quorum resolve the first runoff round, counted as votes were cast
+    proposal.finalized_at = clock.unix_timestamp;
+    if proposal.kind == ProposalKind::RankedChoice && proposal.quorum_reached(&dao.governance) {
+        proposal.resolve_runoff(&dao.governance, clock.unix_timestamp)?;
+    } else {
+        let winning_option = proposal.outcome(&dao.governance);
+        proposal.conclude(winning_option, &dao.governance, clock.unix_timestamp)?;
+    }
+    
+    // Store the final state
+    proposal.serialize(&mut &mut proposal_account.data.borrow_mut()[..])?;
+    
+    msg!(
+        "Proposal {} finalized as {:?} (winning option: {:?})",
+        proposal_account.key,
+        proposal.state,
+        proposal.winning_option
+    );
+    Ok(())
+}
+
+// Process the count ranked votes instruction
+pub fn process_count_ranked_votes(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    
+    // Get the required accounts
+    let proposal_account = next_account_info(account_info_iter)?;
+    let dao_account = next_account_info(account_info_iter)?;
+    
+    // Load the proposal and the DAO it belongs to
+    let mut proposal: Proposal = load_account(program_id, proposal_account)?;
+    let dao = load_dao(program_id, dao_account, &proposal.dao_id)?;
+    
+    if proposal.state != ProposalState::Tallying {
+        return Err(DaoError::ProposalNotTallying.into());
+    }
+    let ballot_weight = proposal.ballot_weight();
+    let runoff = proposal.runoff.as_mut().ok_or(DaoError::ProposalNotTallying)?;
+    
+    // Count each ballot once per round for its highest ranked option still in the race
+    for vote_account in account_info_iter {
+        let mut vote: Vote = load_account(program_id, vote_account)?;
+        let (expected_vote_pubkey, _) =
+            pda::find_vote_address(program_id, proposal_account.key, &vote.voter);
+        if *vote_account.key != expected_vote_pubkey {
+            return Err(DaoError::InvalidAccountAddress.into());
+        }
+        
+        let VoteChoice::Ranking(ranking) = &vote.choice else {
+            continue;
+        };
+        if vote.counted_round == runoff.round {
+            continue;
+        }
+        
+        runoff.count(ranking, vote.weight)?;
+        vote.counted_round = runoff.round;
+        vote.serialize(&mut &mut vote_account.data.borrow_mut()[..])?;
+    }
+    
+    // Resolve the round once every ballot is counted
+    let (round, counted_weight) = (runoff.round, runoff.counted_weight);
+    if counted_weight as u128 == ballot_weight {
+        let clock = Clock::get()?;
+        proposal.resolve_runoff(&dao.governance, clock.unix_timestamp)?;
+    }
+    
+    // Store the runoff progress
+    proposal.serialize(&mut &mut proposal_account.data.borrow_mut()[..])?;
+    
+    msg!(
+        "Counted {} of {} ballot weight in round {} of proposal {} ({:?})",
+        counted_weight,
+        ballot_weight,
+        round,
+        proposal_account.key,
+        proposal.state
+    );
+    Ok(())
+}
+
+// Process the deposit instruction
+pub fn process_deposit(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    amount: u64,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    
+    // Get the required accounts
+    let depositor_account = next_account_info(account_info_iter)?;
+    let dao_account = next_account_info(account_info_iter)?;
+    let treasury_account = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    
+    // Verify the depositor is signer
+    if !depositor_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    
+    // Verify the DAO and its treasury
+    let dao: Dao = load_account(program_id, dao_account)?;
+    check_treasury(program_id, dao_account.key, treasury_account)?;
+    
+    if amount == 0 {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    
+    // Transfer lamports to the treasury
+    invoke(
+        &system_instruction::transfer(depositor_account.key, treasury_account.key, amount),
+        &[depositor_account.clone(), treasury_account.clone(), system_program.clone()],
+    )?;
+    
+    msg!("Deposited {} lamports into the treasury of DAO {}", amount, dao.name);
+    Ok(())
+}
+
+// Process the deposit token instruction
+pub fn process_deposit_token(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    amount: u64,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    
+    // Get the required accounts
+    let depositor_account = next_account_info(account_info_iter)?;
+    let depositor_token_account = next_account_info(account_info_iter)?;
+    let dao_account = next_account_info(account_info_iter)?;
+    let treasury_account = next_account_info(account_info_iter)?;
+    let treasury_token_account = next_account_info(account_info_iter)?;
+    let mint_account = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    let token_program = next_account_info(account_info_iter)?;
+    let associated_token_program = next_account_info(account_info_iter)?;
+    
+    // Verify the depositor is signer
+    if !depositor_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    
+    // Verify the DAO and its treasury
+    let dao: Dao = load_account(program_id, dao_account)?;
+    check_treasury(program_id, dao_account.key, treasury_account)?;
+    
+    if amount == 0 {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    
+    // Create the treasury token account if needed and transfer the tokens
+    create_associated_token_account(
+        depositor_account,
+        treasury_account,
+        treasury_token_account,
+        mint_account,
+        system_program,
+        token_program,
+        associated_token_program,
+    )?;
+    transfer_tokens(
+        depositor_token_account,
+        treasury_token_account,
+        mint_account,
+        depositor_account,
+        token_program,
+        amount,
+        &[],
+    )?;
+    
+    msg!(
+        "Deposited {} tokens of mint {} into the treasury of DAO {}",
+        amount,
+        mint_account.key,
+        dao.name
+    );
+    Ok(())
+}
+
+// Process the execute proposal instruction
+pub fn process_execute_proposal(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    
+    // Get the required accounts
+    let executor_account = next_account_info(account_info_iter)?;
+    let proposal_account = next_account_info(account_info_iter)?;
+    let dao_account = next_account_info(account_info_iter)?;
+    let treasury_account = next_account_info(account_info_iter)?;
+    let recipient_account = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    
+    // Verify the executor is signer
+    if !executor_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    
+    // Load the proposal and the DAO it belongs to
+    let mut proposal: Proposal = load_account(program_id, proposal_account)?;
+    load_dao(program_id, dao_account, &proposal.dao_id)?;
+    let treasury_bump = check_treasury(program_id, dao_account.key, treasury_account)?;
+    
+    // Only queued proposals can be executed, once their execution delay has elapsed
+    let clock = Clock::get()?;
+    proposal.check_executable(clock.unix_timestamp)?;
+    
+    let treasury_seeds: &[&[u8]] =
+        &[pda::TREASURY_SEED, dao_account.key.as_ref(), &[treasury_bump]];
+    
+    // Pay out the treasury withdrawal
+    if let Some(withdrawal) = proposal.withdrawal {
+        if *recipient_account.key != withdrawal.recipient {
+            return Err(DaoError::InvalidWithdrawal.into());
+        }
+        
+        match withdrawal.mint {
+            None => {
+                // Keep the treasury rent exempt
+                let rent = Rent::get()?;
+                let available = treasury_account
+                    .lamports()
+                    .saturating_sub(rent.minimum_balance(0));
+                if withdrawal.amount > available {
+                    return Err(DaoError::InsufficientFunds.into());
+                }
+                
+                invoke_signed(
+                    &system_instruction::transfer(
+                        treasury_account.key,
+                        recipient_account.key,
+                        withdrawal.amount,
+                    ),
+                    &[treasury_account.clone(), recipient_account.clone(), system_program.clone()],
+                    &[treasury_seeds],
+                )?;
+                msg!("Withdrew {} lamports from the treasury", withdrawal.amount);
+            }
+            Some(mint) => {
+                let treasury_token_account = next_account_info(account_info_iter)?;
+                let recipient_token_account = next_account_info(account_info_iter)?;
+                let mint_account = next_account_info(account_info_iter)?;
+                let token_program = next_account_info(account_info_iter)?;
+                let associated_token_program = next_account_info(account_info_iter)?;
+                
+                if *mint_account.key != mint
+                    || *treasury_token_account.key
+                        != get_associated_token_address(treasury_account.key, &mint)
+                {
+                    return Err(DaoError::InvalidTokenAccount.into());
+                }
+                
+                // Create the recipient token account if needed and transfer the tokens
+                create_associated_token_account(
+                    executor_account,
+                    recipient_account,
+                    recipient_token_account,
+                    mint_account,
+                    system_program,
+                    token_program,
+                    associated_token_program,
+                )?;
+                transfer_tokens(
+                    treasury_token_account,
+                    recipient_token_account,
+                    mint_account,
+                    treasury_account,
+                    token_program,
+                    withdrawal.amount,
+                    &[treasury_seeds],
+                )?;
+                msg!("Withdrew {} tokens of mint {} from the treasury", withdrawal.amount, mint);
+            }
+        }
+    }
+    
+    // Invoke the proposal instructions in order, signing as the treasury
+    for instruction in proposal.instructions.iter_mut() {
+        let program_account = find_account_info(accounts, &instruction.program_id)?;
+        let mut account_infos = vec![program_account.clone()];
+        for meta in &instruction.accounts {
+            account_infos.push(find_account_info(accounts, &meta.pubkey)?.clone());
+        }
+        invoke_signed(&Instruction::from(&*instruction), &account_infos, &[treasury_seeds])?;
+        instruction.executed = true;
+    }
+    
+    // Mark the proposal as executed
+    proposal.state = ProposalState::Executed;
+    proposal.serialize(&mut &mut proposal_account.data.borrow_mut()[..])?;
+    
+    msg!("Proposal {} executed successfully", proposal_account.key);
+    Ok(())
+}
+
+// Process the cancel proposal instruction
+pub fn process_cancel_proposal(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    
+    // Get the required accounts
+    let authority_account = next_account_info(account_info_iter)?;
+    let proposal_account = next_account_info(account_info_iter)?;
+    let dao_account = next_account_info(account_info_iter)?;
+    
+    // Verify the authority is signer
+    if !authority_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    
+    // Load the proposal and the DAO it belongs to
+    let mut proposal: Proposal = load_account(program_id, proposal_account)?;
+    let dao = load_dao(program_id, dao_account, &proposal.dao_id)?;
+    
+    // Only the DAO authority can cancel proposals
+    if *authority_account.key != dao.authority {
+        return Err(DaoError::Unauthorized.into());
+    }
+    
+    // Proposals can only be cancelled while they wait for execution
+    if proposal.state != ProposalState::Queued {
+        return Err(DaoError::ProposalNotQueued.into());
+    }
+    let clock = Clock::get()?;
+    if clock.unix_timestamp >= proposal.executable_at {
+        return Err(DaoError::TimelockExpired.into());
+    }
+    
+    proposal.state = ProposalState::Cancelled;
+    proposal.serialize(&mut &mut proposal_account.data.borrow_mut()[..])?;
+    
+    msg!("Proposal {} cancelled", proposal_account.key);
+    Ok(())
+}
+
+// Process the create pod instruction
+pub fn process_create_pod(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    dao_id: Pubkey,
+    name: String,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    
+    // Get the required accounts
+    let authority_account = next_account_info(account_info_iter)?;
+    let pod_account = next_account_info(account_info_iter)?;
+    let dao_account = next_account_info(account_info_iter)?;
+    let module_account = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    
+    // Verify the authority is signer
+    if !authority_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    
+    // Only the DAO authority can create pods, once the POD module is enabled
+    let dao = load_dao(program_id, dao_account, &dao_id)?;
+    if *authority_account.key != dao.authority {
+        return Err(DaoError::Unauthorized.into());
+    }
+    check_module_enabled(program_id, module_account, &dao_id, ModuleType::Pod)?;
+    
+    // Verify the pod account is the expected PDA
+    let (expected_pod_pubkey, pod_bump) = pda::find_pod_address(program_id, &dao_id, &name);
+    if *pod_account.key != expected_pod_pubkey {
+        return Err(DaoError::InvalidAccountAddress.into());
+    }
+    let name_seed = pda::name_seed(&name);
+    
+    let pod = Pod {
+        account_type: AccountType::Pod,
+        dao_id,
+        authority: *authority_account.key,
+        name,
+        members: Vec::new(),
+    };
+    let space = pod.max_space();
+    create_pda_account(
+        authority_account,
+        pod_account,
+        system_program,
+        program_id,
+        Rent::get()?.minimum_balance(space),
+        space as u64,
+        &[pda::POD_SEED, dao_id.as_ref(), &name_seed, &[pod_bump]],
+    )?;
+    pod.serialize(&mut &mut pod_account.data.borrow_mut()[..])?;
+    
+    msg!("Pod created successfully with ID: {}", pod_account.key);
+    Ok(())
+}
+
+// Process the add pod member instruction
+pub fn process_add_pod_member(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    member: Pubkey,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    
+    // Get the required accounts
+    let authority_account = next_account_info(account_info_iter)?;
+    let pod_account = next_account_info(account_info_iter)?;
+    let module_account = next_account_info(account_info_iter)?;
+    
+    // Verify the authority is signer
+    if !authority_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    
+    // Only the pod authority can add members, while the POD module is enabled
+    let mut pod: Pod = load_account(program_id, pod_account)?;
+    if *authority_account.key != pod.authority {
+        return Err(DaoError::Unauthorized.into());
+    }
+    check_module_enabled(program_id, module_account, &pod.dao_id, ModuleType::Pod)?;
+    
+    pod.add_member(member)?;
+    pod.serialize(&mut &mut pod_account.data.borrow_mut()[..])?;
+    
+    msg!("Added {} to pod {}", member, pod_account.key);
+    Ok(())
+}
+
+// Process the remove pod member instruction
+pub fn process_remove_pod_member(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    member: Pubkey,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    
+    // Get the required accounts
+    let authority_account = next_account_info(account_info_iter)?;
+    let pod_account = next_account_info(account_info_iter)?;
+    let module_account = next_account_info(account_info_iter)?;
+    
+    // Verify the authority is signer
+    if !authority_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    
+    // Only the pod authority can remove members, while the POD module is enabled
+    let mut pod: Pod = load_account(program_id, pod_account)?;
+    if *authority_account.key != pod.authority {
+        return Err(DaoError::Unauthorized.into());
+    }
+    check_module_enabled(program_id, module_account, &pod.dao_id, ModuleType::Pod)?;
+    
+    pod.remove_member(&member)?;
+    pod.serialize(&mut &mut pod_account.data.borrow_mut()[..])?;
+    
+    msg!("Removed {} from pod {}", member, pod_account.key);
+    Ok(())
+}
+
+// Process the endorse instruction
+pub fn process_endorse(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    dao_id: Pubkey,
+    member: Pubkey,
 ) -> ProgramResult {
     let account_info_iter = &mut accounts.iter();
     
     // Get the required accounts
-    let creator_account = next_account_info(account_info_iter)?;
-    let featured_account = next_account_info(account_info_iter)?;
+    let endorser_account = next_account_info(account_info_iter)?;
+    let endorser_reputation_account = next_account_info(account_info_iter)?;
+    let member_reputation_account = next_account_info(account_info_iter)?;
     let dao_account = next_account_info(account_info_iter)?;
+    let module_account = next_account_info(account_info_iter)?;
     let system_program = next_account_info(account_info_iter)?;
-    let fee_account = next_account_info(account_info_iter)?;
+    let endorsement_account = next_account_info(account_info_iter)?;
+    
+    // Verify the endorser is signer
+    if !endorser_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    if member == *endorser_account.key {
+        return Err(DaoError::InvalidEndorsement.into());
+    }
+    
+    // Verify the DAO exists and has the POL module enabled
+    let dao = load_dao(program_id, dao_account, &dao_id)?;
+    check_module_enabled(program_id, module_account, &dao_id, ModuleType::Pol)?;
+    
+    // In token DAOs, both members must hold the governance token
+    if let Some(mint) = dao.governance_mint() {
+        let endorser_token_account = next_account_info(account_info_iter)?;
+        let member_token_account = next_account_info(account_info_iter)?;
+        token_vote_weight(endorser_account.key, &mint, endorser_token_account)?;
+        token_vote_weight(&member, &mint, member_token_account)?;
+    }
+    
+    let now = Clock::get()?.unix_timestamp;
+    let mut endorser_reputation = load_or_create_reputation(
+        program_id,
+        endorser_account,
+        endorser_reputation_account,
+        system_program,
+        &dao_id,
+        endorser_account.key,
+        now,
+    )?;
+    
+    // Only the DAO authority can endorse without reputation of their own
+    if *endorser_account.key != dao.authority
+        && endorser_reputation.score_at(now) < MIN_ENDORSER_REPUTATION
+    {
+        return Err(DaoError::InsufficientReputation.into());
+    }
+    
+    let mut member_reputation = load_or_create_reputation(
+        program_id,
+        endorser_account,
+        member_reputation_account,
+        system_program,
+        &dao_id,
+        &member,
+        now,
+    )?;
+    
+    let mut endorsement = load_or_create_endorsement(
+        program_id,
+        endorser_account,
+        endorsement_account,
+        system_program,
+        &dao_id,
+        endorser_account.key,
+        &member,
+    )?;
+    
+    endorser_reputation.record_endorsement_given(now)?;
+    endorsement.record(now)?;
+    member_reputation.endorse(now)?;
+    
+    endorser_reputation.serialize(&mut &mut endorser_reputation_account.data.borrow_mut()[..])?;
+    endorsement.serialize(&mut &mut endorsement_account.data.borrow_mut()[..])?;
+    member_reputation.serialize(&mut &mut member_reputation_account.data.borrow_mut()[..])?;
+    
+    msg!(
+        "{} endorsed {}, reputation now {}",
+        endorser_account.key,
+        member,
+        member_reputation.score
+    );
+    Ok(())
+}
+
+// Process the update DAO permissions instruction
+pub fn process_update_dao_permissions(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    module_manager: Option<Pubkey>,
+    allow_featured_sponsors: bool,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    
+    // Get the required accounts
+    let authority_account = next_account_info(account_info_iter)?;
+    let dao_account = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    
+    // Verify the authority is signer
+    if !authority_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    
+    // Only the DAO authority can change the DAO's permissions
+    let mut dao: Dao = load_account(program_id, dao_account)?;
+    if *authority_account.key != dao.authority {
+        return Err(DaoError::Unauthorized.into());
+    }
+    
+    dao.module_manager = module_manager;
+    dao.allow_featured_sponsors = allow_featured_sponsors;
+    
+    // Setting a module manager grows the DAO account
+    let space = dao.try_to_vec()?.len();
+    resize_program_account(dao_account, authority_account, system_program, space)?;
+    dao.serialize(&mut &mut dao_account.data.borrow_mut()[..])?;
+    
+    msg!("DAO {} permissions updated", dao_account.key);
+    Ok(())
+}
+
+// Process the initialize config instruction
+#[allow(clippy::too_many_arguments)]
+pub fn process_initialize_config(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    admin: Pubkey,
+    fee_recipient: Pubkey,
+    fees: FeeSchedule,
+    stable_mints: Vec<Pubkey>,
+    fee_split: FeeSplit,
+    featured_auction: FeaturedAuctionConfig,
+    featured_tiers: Vec<FeaturedTier>,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    
+    // Get the required accounts
+    let authority_account = next_account_info(account_info_iter)?;
+    let config_account = next_account_info(account_info_iter)?;
+    let program_data_account = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    
+    // Verify the authority is signer
+    if !authority_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    
+    // Only the program's upgrade authority can create the config
+    if program_upgrade_authority(program_id, program_data_account)? != Some(*authority_account.key)
+    {
+        return Err(DaoError::Unauthorized.into());
+    }
+    
+    // Verify the config account is the expected PDA
+    let (expected_config_pubkey, config_bump) = pda::find_config_address(program_id);
+    if *config_account.key != expected_config_pubkey {
+        return Err(DaoError::InvalidAccountAddress.into());
+    }
+    
+    // Create config data structure
+    let config = Config {
+        account_type: AccountType::Config,
+        admin,
+        fee_recipient,
+        fees,
+        stable_mints,
+        fee_split,
+        featured_auction,
+        featured_tiers,
+    };
+    config.validate()?;
+    
+    // Calculate space required for the config account
+    let config_serialized = config.try_to_vec()?;
+    let space = config_serialized.len() as u64;
+    
+    // Calculate the rent required
+    let rent = Rent::get()?;
+    let rent_lamports = rent.minimum_balance(space as usize);
+    
+    // Create the config account
+    create_pda_account(
+        authority_account,
+        config_account,
+        system_program,
+        program_id,
+        rent_lamports,
+        space,
+        &[pda::CONFIG_SEED, &[config_bump]],
+    )?;
+    
+    // Serialize and store the config data
+    config.serialize(&mut &mut config_account.data.borrow_mut()[..])?;
+    
+    msg!("Config initialized with admin {}", admin);
+    Ok(())
+}
+
+// Process the update config instruction
+#[allow(clippy::too_many_arguments)]
+pub fn process_update_config(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    admin: Pubkey,
+    fee_recipient: Pubkey,
+    fees: FeeSchedule,
+    stable_mints: Vec<Pubkey>,
+    fee_split: FeeSplit,
+    featured_auction: FeaturedAuctionConfig,
+    featured_tiers: Vec<FeaturedTier>,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    
+    // Get the required accounts
+    let admin_account = next_account_info(account_info_iter)?;
+    let config_account = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    
+    // Verify the admin is signer
+    if !admin_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    
+    // Only the current admin can update the config
+    let mut config = load_config(program_id, config_account)?;
+    if *admin_account.key != config.admin {
+        return Err(DaoError::Unauthorized.into());
+    }
+    
+    config.admin = admin;
+    config.fee_recipient = fee_recipient;
+    config.fees = fees;
+    config.stable_mints = stable_mints;
+    config.fee_split = fee_split;
+    config.featured_auction = featured_auction;
+    config.featured_tiers = featured_tiers;
+    config.validate()?;
+    
+    // Resize the config account to fit the new data, keeping it rent exempt
+    let space = config.try_to_vec()?.len();
+    resize_program_account(config_account, admin_account, system_program, space)?;
+    
+    config.serialize(&mut &mut config_account.data.borrow_mut()[..])?;
+    
+    msg!("Config updated");
+    Ok(())
+}
+
+// Resize a program account, with the payer topping up its rent when it grows
+fn resize_program_account<'a>(
+    account: &AccountInfo<'a>,
+    payer: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    space: usize,
+) -> ProgramResult {
+    if space == account.data_len() {
+        return Ok(());
+    }
+    let rent_lamports = Rent::get()?.minimum_balance(space);
+    let top_up = rent_lamports.saturating_sub(account.lamports());
+    if top_up > 0 {
+        invoke(
+            &system_instruction::transfer(payer.key, account.key, top_up),
+            &[payer.clone(), account.clone(), system_program.clone()],
+        )?;
+    }
+    account.realloc(space, false)
+}
+
+// Create the associated token account of a wallet for a mint, unless it already exists
+fn create_associated_token_account<'a>(
+    payer: &AccountInfo<'a>,
+    wallet: &AccountInfo<'a>,
+    token_account: &AccountInfo<'a>,
+    mint_account: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    token_program: &AccountInfo<'a>,
+    associated_token_program: &AccountInfo<'a>,
+) -> ProgramResult {
+    if *token_program.key != spl_token::id()
+        || *associated_token_program.key != spl_associated_token_account::id()
+    {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    if *token_account.key != get_associated_token_address(wallet.key, mint_account.key) {
+        return Err(DaoError::InvalidTokenAccount.into());
+    }
+    
+    invoke(
+        &create_associated_token_account_idempotent(
+            payer.key,
+            wallet.key,
+            mint_account.key,
+            token_program.key,
+        ),
+        &[
+            payer.clone(),
+            token_account.clone(),
+            wallet.clone(),
+            mint_account.clone(),
+            system_program.clone(),
+            token_program.clone(),
+            associated_token_program.clone(),
+        ],
+    )
+}
+
+// Escrow the tokens backing a vote in the vote account's associated token account
+#[allow(clippy::too_many_arguments)]
+fn lock_vote_tokens<'a>(
+    voter: &AccountInfo<'a>,
+    voter_token_account: &AccountInfo<'a>,
+    vote_account: &AccountInfo<'a>,
+    escrow_account: &AccountInfo<'a>,
+    mint_account: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    token_program: &AccountInfo<'a>,
+    associated_token_program: &AccountInfo<'a>,
+    amount: u64,
+) -> ProgramResult {
+    create_associated_token_account(
+        voter,
+        vote_account,
+        escrow_account,
+        mint_account,
+        system_program,
+        token_program,
+        associated_token_program,
+    )?;
+    transfer_tokens(
+        voter_token_account,
+        escrow_account,
+        mint_account,
+        voter,
+        token_program,
+        amount,
+        &[],
+    )
+}
+
+// Transfer SPL tokens, signing with the given seeds when the authority is a PDA
+fn transfer_tokens<'a>(
+    source: &AccountInfo<'a>,
+    destination: &AccountInfo<'a>,
+    mint_account: &AccountInfo<'a>,
+    authority: &AccountInfo<'a>,
+    token_program: &AccountInfo<'a>,
+    amount: u64,
+    signer_seeds: &[&[&[u8]]],
+) -> ProgramResult {
+    if *token_program.key != spl_token::id() {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    let mint = Mint::unpack(&mint_account.data.borrow())?;
+    
+    invoke_signed(
+        &spl_token::instruction::transfer_checked(
+            token_program.key,
+            source.key,
+            mint_account.key,
+            destination.key,
+            authority.key,
+            &[],
+            amount,
+            mint.decimals,
+        )?,
+        &[
+            source.clone(),
+            mint_account.clone(),
+            destination.clone(),
+            authority.clone(),
+            token_program.clone(),
+        ],
+        signer_seeds,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_program::{instruction::Instruction, program_stubs, system_program};
+    use spl_token::{error::TokenError, instruction::TokenInstruction};
+    use std::sync::Once;
+    
+    // Unix timestamp returned by `Clock::get()` in tests
+    const TEST_NOW: i64 = 150;
+    
+    // Syscall stubs providing the Clock and Rent sysvars to the processors
+    struct TestSyscallStubs;
+    
+    impl program_stubs::SyscallStubs for TestSyscallStubs {
+        fn sol_get_clock_sysvar(&self, var_addr: *mut u8) -> u64 {
+            let clock = Clock {
+                unix_timestamp: TEST_NOW,
+                ..Clock::default()
+            };
+            unsafe { *(var_addr as *mut Clock) = clock };
+            solana_program::entrypoint::SUCCESS
+        }
+        
+        fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+            unsafe { *(var_addr as *mut Rent) = Rent::default() };
+            solana_program::entrypoint::SUCCESS
+        }
+        
+        // Token transfers between initialized token accounts move their balances, every
+        // other cross-program invocation is a no-op
+        fn sol_invoke_signed(
+            &self,
+            instruction: &Instruction,
+            account_infos: &[AccountInfo],
+            _signers_seeds: &[&[&[u8]]],
+        ) -> ProgramResult {
+            if instruction.program_id != spl_token::id() {
+                return Ok(());
+            }
+            let account = |index: usize| {
+                let key = instruction.accounts[index].pubkey;
+                account_infos.iter().find(|info| *info.key == key).unwrap()
+            };
+            let TokenInstruction::TransferChecked { amount, .. } =
+                TokenInstruction::unpack(&instruction.data)?
+            else {
+                return Ok(());
+            };
+            let (source, destination, authority) = (account(0), account(2), account(3));
+            let (Ok(mut from), Ok(mut to)) = (
+                TokenAccount::unpack(&source.data.borrow()),
+                TokenAccount::unpack(&destination.data.borrow()),
+            ) else {
+                return Ok(());
+            };
+            if from.owner != *authority.key {
+                return Err(TokenError::OwnerMismatch.into());
+            }
+            from.amount = from.amount.checked_sub(amount).ok_or(TokenError::InsufficientFunds)?;
+            to.amount += amount;
+            TokenAccount::pack(from, &mut source.data.borrow_mut())?;
+            TokenAccount::pack(to, &mut destination.data.borrow_mut())
+        }
+    }
+    
+    fn setup_syscall_stubs() {
+        static ONCE: Once = Once::new();
+        ONCE.call_once(|| {
+            program_stubs::set_syscall_stubs(Box::new(TestSyscallStubs));
+        });
+    }
+    
+    // Owned account state that can be lent out as an `AccountInfo`
+    struct TestAccount {
+        key: Pubkey,
+        owner: Pubkey,
+        lamports: u64,
+        data: Vec<u8>,
+    }
+    
+    impl TestAccount {
+        fn new(key: Pubkey, owner: Pubkey, data: Vec<u8>) -> Self {
+            Self { key, owner, lamports: 0, data }
+        }
+        
+        fn info(&mut self, is_signer: bool, is_writable: bool) -> AccountInfo<'_> {
+            AccountInfo::new(
+                &self.key,
+                is_signer,
+                is_writable,
+                &mut self.lamports,
+                &mut self.data,
+                &self.owner,
+                false,
+                0,
+            )
+        }
+    }
+    
+    fn test_dao() -> Dao {
+        Dao {
+            account_type: AccountType::Dao,
+            authority: Pubkey::new_unique(),
+            name: "DAO".to_string(),
+            description: String::new(),
+            discord_server: String::new(),
+            twitter: String::new(),
+            telegram: String::new(),
+            instagram: String::new(),
+            tiktok: String::new(),
+            website: String::new(),
+            treasury: Pubkey::new_unique(),
+            profile: String::new(),
+            token_address: String::new(),
+            governance: GovernanceConfig {
+                quorum: 10,
+                approval_threshold_bps: 6_000,
+                execution_delay: 100,
+                voting_mode: VotingMode::Token,
+            },
+            module_manager: None,
+            allow_featured_sponsors: false,
+        }
+    }
+    
+    fn test_proposal() -> Proposal {
+        Proposal {
+            account_type: AccountType::Proposal,
+            authority: Pubkey::new_unique(),
+            name: "Proposal".to_string(),
+            description: String::new(),
+            dao_id: Pubkey::new_unique(),
+            pod_id: String::new(),
+            start_time: 100,
+            end_time: 200,
+            kind: ProposalKind::YesNo,
+            options: proposal_options(vec![], false).unwrap().1,
+            votes_abstain: 0,
+            weight_abstain: 0,
+            state: ProposalState::Voting,
+            finalized_at: 0,
+            executable_at: 0,
+            withdrawal: None,
+            instructions: vec![],
+            winning_option: None,
+            runoff: None,
+        }
+    }
+    
+    fn proposal_test_account(owner: Pubkey) -> TestAccount {
+        TestAccount::new(Pubkey::new_unique(), owner, test_proposal().try_to_vec().unwrap())
+    }
+    
+    // Config without proposal or vote fees, so those instructions need no fee accounts
+    fn test_config() -> Config {
+        Config {
+            account_type: AccountType::Config,
+            admin: Pubkey::new_unique(),
+            fee_recipient: Pubkey::new_unique(),
+            fees: FeeSchedule {
+                create_dao: 2_000,
+                featured_per_day: 2_000,
+                pod_module: 2_000,
+                pol_module: 2_000,
+                create_proposal: 0,
+                vote: 0,
+            },
+            stable_mints: vec![Pubkey::new_unique()],
+            fee_split: FeeSplit {
+                referrer_bps: 1_000,
+                treasury_rebate_bps: 2_500,
+            },
+            featured_auction: FeaturedAuctionConfig {
+                slots: 0,
+                period_days: 7,
+            },
+            featured_tiers: vec![],
+        }
+    }
+    
+    fn config_test_account(program_id: &Pubkey, config: &Config) -> TestAccount {
+        let (key, _) = pda::find_config_address(program_id);
+        TestAccount::new(key, *program_id, config.try_to_vec().unwrap())
+    }
+    
+    // Associated token account of `owner` for `mint`, holding `amount` tokens
+    fn token_test_account(owner: &Pubkey, mint: &Pubkey, amount: u64) -> TestAccount {
+        let mut data = vec![0; TokenAccount::LEN];
+        let token_account = TokenAccount {
+            mint: *mint,
+            owner: *owner,
+            amount,
+            state: spl_token::state::AccountState::Initialized,
+            ..TokenAccount::default()
+        };
+        TokenAccount::pack(token_account, &mut data).unwrap();
+        TestAccount::new(get_associated_token_address(owner, mint), spl_token::id(), data)
+    }
+    
+    fn token_balance(token_account: &TestAccount) -> u64 {
+        TokenAccount::unpack(&token_account.data).unwrap().amount
+    }
+    
+    fn create_proposal_ix_result(
+        program_id: &Pubkey,
+        dao: &mut TestAccount,
+        dao_id: Pubkey,
+    ) -> ProgramResult {
+        let mut creator = TestAccount::new(Pubkey::new_unique(), system_program::id(), vec![]);
+        let mut proposal = TestAccount::new(Pubkey::new_unique(), system_program::id(), vec![]);
+        let mut system = TestAccount::new(system_program::id(), Pubkey::default(), vec![]);
+        let mut config = config_test_account(program_id, &test_config());
+        let accounts = [
+            creator.info(true, true),
+            proposal.info(false, true),
+            dao.info(false, false),
+            system.info(false, false),
+            config.info(false, false),
+        ];
+        process_create_proposal(
+            program_id,
+            &accounts,
+            "Proposal".to_string(),
+            String::new(),
+            dao_id,
+            String::new(),
+            100,
+            200,
+            None,
+            vec![],
+            vec![],
+            false,
+        )
+    }
+    
+    fn execute_proposal_ix_result(
+        program_id: &Pubkey,
+        proposal: &Proposal,
+        recipient: Pubkey,
+    ) -> ProgramResult {
+        let dao_id = proposal.dao_id;
+        let proposal_data = proposal.try_to_vec().unwrap();
+        let mut proposal = TestAccount::new(Pubkey::new_unique(), *program_id, proposal_data);
+        let mut dao = TestAccount::new(dao_id, *program_id, test_dao().try_to_vec().unwrap());
+        let (treasury, _) = pda::find_treasury_address(program_id, &dao_id);
+        let mut treasury = TestAccount::new(treasury, system_program::id(), vec![]);
+        
+        let mut executor = TestAccount::new(Pubkey::new_unique(), system_program::id(), vec![]);
+        let mut recipient = TestAccount::new(recipient, system_program::id(), vec![]);
+        let mut system = TestAccount::new(system_program::id(), Pubkey::default(), vec![]);
+        let accounts = [
+            executor.info(true, true),
+            proposal.info(false, true),
+            dao.info(false, false),
+            treasury.info(false, true),
+            recipient.info(false, true),
+            system.info(false, false),
+        ];
+        process_execute_proposal(program_id, &accounts)
+    }
+    
+    fn vote_ix_result(
+        program_id: &Pubkey,
+        voter: Pubkey,
+        vote_account: &mut TestAccount,
+        proposal: &mut TestAccount,
+        proposal_id: String,
+    ) -> ProgramResult {
+        // DAO without a governance mint that the proposal belongs to
+        let dao_id = Proposal::try_from_slice(&proposal.data)
+            .map(|proposal| proposal.dao_id)
+            .unwrap_or_default();
+        let mut dao = TestAccount::new(dao_id, *program_id, test_dao().try_to_vec().unwrap());
+        
+        let mut voter = TestAccount::new(voter, system_program::id(), vec![]);
+        let mut system = TestAccount::new(system_program::id(), Pubkey::default(), vec![]);
+        let mut config = config_test_account(program_id, &test_config());
+        let accounts = [
+            voter.info(true, true),
+            vote_account.info(false, true),
+            proposal.info(false, true),
+            system.info(false, false),
+            dao.info(false, false),
+            config.info(false, false),
+        ];
+        process_vote(program_id, &accounts, VoteChoice::Option(0), proposal_id)
+    }
+    
+    #[test]
+    fn test_calculate_fee() {
+        // SOL price = $100.00 (10000 cents)
+        // $20 / $100 = 0.2 SOL = 200_000_000 lamports
+        let fee = calculate_fee_in_lamports(2_000, 10000).unwrap();
+        assert_eq!(fee, 200_000_000);
+        
+        // SOL price = $50.00 (5000 cents)
+        // $20 / $50 = 0.4 SOL = 400_000_000 lamports
+        let fee = calculate_fee_in_lamports(2_000, 5000).unwrap();
+        assert_eq!(fee, 400_000_000);
+        
+        // SOL price = $200.00 (20000 cents)
+        // $0.75 / $200 = 0.00375 SOL = 3_750_000 lamports
+        let fee = calculate_fee_in_lamports(75, 20000).unwrap();
+        assert_eq!(fee, 3_750_000);
+        
+        // $5 per day for 7 days = $35
+        assert_eq!(calculate_featured_fee_usd_cents(500, &[], 7), Ok(3_500));
+        assert_eq!(
+            calculate_featured_fee_usd_cents(500, &[], 366),
+            Err(ProgramError::InvalidInstructionData)
+        );
+    }
+    
+    #[test]
+    fn test_vote_address_is_unique_per_voter() {
+        let program_id = Pubkey::new_unique();
+        let proposal = Pubkey::new_unique();
+        let voter = Pubkey::new_unique();
+        
+        // The same (proposal, voter) pair always maps to the same account
+        let (first, _) = pda::find_vote_address(&program_id, &proposal, &voter);
+        let (second, _) = pda::find_vote_address(&program_id, &proposal, &voter);
+        assert_eq!(first, second);
+        
+        // A different voter gets a different account
+        let (other, _) = pda::find_vote_address(&program_id, &proposal, &Pubkey::new_unique());
+        assert_ne!(first, other);
+    }
+    
+    #[test]
+    fn test_proposal_record_vote() {
+        let mut proposal = test_proposal();
+        
+        proposal.record_vote(&VoteChoice::Option(0), 1).unwrap();
+        proposal.record_vote(&VoteChoice::Option(0), 1).unwrap();
+        proposal.record_vote(&VoteChoice::Option(1), 1).unwrap();
+        proposal.record_vote(&VoteChoice::Abstain, 3).unwrap();
+        let tallies = |option: &ProposalOption| (option.votes, option.weight);
+        assert_eq!(proposal.options.iter().map(tallies).collect::<Vec<_>>(), [(2, 2), (1, 1)]);
+        assert_eq!((proposal.votes_abstain, proposal.weight_abstain), (1, 3));
+        
+        // Votes must pick one of the proposal's options
+        assert_eq!(
+            proposal.record_vote(&VoteChoice::Option(2), 1),
+            Err(DaoError::InvalidVote.into())
+        );
+        
+        // Tallies never wrap around
+        proposal.options[1].weight = u64::MAX;
+        assert_eq!(
+            proposal.record_vote(&VoteChoice::Option(1), 1),
+            Err(ProgramError::ArithmeticOverflow)
+        );
+    }
+    
+    #[test]
+    fn test_proposal_voting_window() {
+        let proposal = test_proposal();
+        
+        assert_eq!(proposal.check_voting_window(99), Err(DaoError::VotingNotStarted.into()));
+        assert_eq!(proposal.check_voting_window(100), Ok(()));
+        assert_eq!(proposal.check_voting_window(200), Ok(()));
+        assert_eq!(proposal.check_voting_window(201), Err(DaoError::VotingEnded.into()));
+    }
+    
+    #[test]
+    fn test_vote_twice_is_rejected() {
+        let program_id = Pubkey::new_unique();
+        let voter = Pubkey::new_unique();
+        let mut proposal = proposal_test_account(program_id);
+        
+        // The vote record for (proposal, voter) already exists
+        let (vote_pubkey, _) = pda::find_vote_address(&program_id, &proposal.key, &voter);
+        let existing_vote = Vote {
+            account_type: AccountType::Vote,
+            voter,
+            choice: VoteChoice::Option(0),
+            proposal_id: proposal.key.to_string(),
+            weight: 1,
+            counted_round: 0,
+            credits_spent: 0,
+            locked_amount: 0,
+            locked_mint: None,
+        };
+        let mut vote_account =
+            TestAccount::new(vote_pubkey, program_id, existing_vote.try_to_vec().unwrap());
+        
+        let proposal_id = proposal.key.to_string();
+        let result =
+            vote_ix_result(&program_id, voter, &mut vote_account, &mut proposal, proposal_id);
+        assert_eq!(result, Err(DaoError::AlreadyVoted.into()));
+    }
+    
+    #[test]
+    fn test_vote_tokens_escrow() {
+        setup_syscall_stubs();
+        let program_id = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let mut dao_data = test_dao();
+        dao_data.token_address = mint.to_string();
+        let mut proposal_data = test_proposal();
+        let mut dao =
+            TestAccount::new(proposal_data.dao_id, program_id, dao_data.try_to_vec().unwrap());
+        let mut proposal =
+            TestAccount::new(Pubkey::new_unique(), program_id, proposal_data.try_to_vec().unwrap());
+        let mut mint_data = vec![0; Mint::LEN];
+        let mint_state = Mint { decimals: 6, is_initialized: true, ..Mint::default() };
+        Mint::pack(mint_state, &mut mint_data).unwrap();
+        let mut mint_account = TestAccount::new(mint, spl_token::id(), mint_data);
+        let mut system = TestAccount::new(system_program::id(), Pubkey::default(), vec![]);
+        let mut token_program = TestAccount::new(spl_token::id(), Pubkey::default(), vec![]);
+        let mut ata_program =
+            TestAccount::new(spl_associated_token_account::id(), Pubkey::default(), vec![]);
+        
+        // The first voter's whole balance is escrowed with their vote
+        let mut voter = TestAccount::new(Pubkey::new_unique(), system_program::id(), vec![]);
+        let mut voter_tokens = token_test_account(&voter.key, &mint, 10);
+        let (vote_key, _) = pda::find_vote_address(&program_id, &proposal.key, &voter.key);
+        let mut escrow = token_test_account(&vote_key, &mint, 0);
+        let vote_data = Vote {
+            account_type: AccountType::Vote,
+            voter: voter.key,
+            choice: VoteChoice::Option(0),
+            proposal_id: proposal.key.to_string(),
+            weight: 10,
+            counted_round: 0,
+            credits_spent: 0,
+            locked_amount: 10,
+            locked_mint: Some(mint),
+        };
+        let mut vote_account =
+            TestAccount::new(vote_key, program_id, vote_data.try_to_vec().unwrap());
+        lock_vote_tokens(
+            &voter.info(true, true),
+            &voter_tokens.info(false, true),
+            &vote_account.info(false, true),
+            &escrow.info(false, true),
+            &mint_account.info(false, false),
+            &system.info(false, false),
+            &token_program.info(false, false),
+            &ata_program.info(false, false),
+            10,
+        )
+        .unwrap();
+        assert_eq!((token_balance(&voter_tokens), token_balance(&escrow)), (0, 10));
+        
+        // So the tokens can't be sent to a second wallet to vote again
+        let other = Pubkey::new_unique();
+        let mut other_tokens = token_test_account(&other, &mint, 0);
+        let result = transfer_tokens(
+            &voter_tokens.info(false, true),
+            &other_tokens.info(false, true),
+            &mint_account.info(false, false),
+            &voter.info(true, false),
+            &token_program.info(false, false),
+            10,
+            &[],
+        );
+        assert_eq!(result, Err(TokenError::InsufficientFunds.into()));
+        let mut other_voter = TestAccount::new(other, system_program::id(), vec![]);
+        let (other_vote_key, _) = pda::find_vote_address(&program_id, &proposal.key, &other);
+        let mut other_vote = TestAccount::new(other_vote_key, system_program::id(), vec![]);
+        let mut other_escrow = token_test_account(&other_vote_key, &mint, 0);
+        let mut config = config_test_account(&program_id, &test_config());
+        let proposal_id = proposal.key.to_string();
+        let accounts = [
+            other_voter.info(true, true),
+            other_vote.info(false, true),
+            proposal.info(false, true),
+            system.info(false, false),
+            dao.info(false, false),
+            config.info(false, false),
+            other_tokens.info(false, true),
+            other_escrow.info(false, true),
+            mint_account.info(false, false),
+            token_program.info(false, false),
+            ata_program.info(false, false),
+        ];
+        let result = process_vote(&program_id, &accounts, VoteChoice::Option(0), proposal_id);
+        assert_eq!(result, Err(DaoError::NoVotingPower.into()));
+        
+        // The tokens are only released once the proposal is finalized
+        let mut release = |proposal: &mut TestAccount| {
+            let accounts = [
+                voter.info(false, true),
+                vote_account.info(false, true),
+                proposal.info(false, false),
+                escrow.info(false, true),
+                voter_tokens.info(false, true),
+                mint_account.info(false, false),
+                token_program.info(false, false),
+            ];
+            process_release_vote_tokens(&program_id, &accounts)
+        };
+        assert_eq!(release(&mut proposal), Err(DaoError::ProposalNotFinalized.into()));
+        proposal_data.state = ProposalState::Rejected;
+        proposal.data = proposal_data.try_to_vec().unwrap();
+        release(&mut proposal).unwrap();
+        assert_eq!(release(&mut proposal), Err(DaoError::NoLockedTokens.into()));
+        assert_eq!((token_balance(&voter_tokens), token_balance(&escrow)), (10, 0));
+        let vote = Vote::deserialize(&mut &vote_account.data[..]).unwrap();
+        assert_eq!(vote.locked_amount, 0);
+    }
+    
+    #[test]
+    fn test_release_vote_tokens_checks_the_locked_mint() {
+        setup_syscall_stubs();
+        let program_id = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let mut proposal_data = test_proposal();
+        proposal_data.state = ProposalState::Rejected;
+        let mut proposal =
+            TestAccount::new(Pubkey::new_unique(), program_id, proposal_data.try_to_vec().unwrap());
+        let mut voter = TestAccount::new(Pubkey::new_unique(), system_program::id(), vec![]);
+        let (vote_key, _) = pda::find_vote_address(&program_id, &proposal.key, &voter.key);
+        let vote_data = Vote {
+            account_type: AccountType::Vote,
+            voter: voter.key,
+            choice: VoteChoice::Option(0),
+            proposal_id: proposal.key.to_string(),
+            weight: 10,
+            counted_round: 0,
+            credits_spent: 0,
+            locked_amount: 10,
+            locked_mint: Some(mint),
+        };
+        let mut vote_account =
+            TestAccount::new(vote_key, program_id, vote_data.try_to_vec().unwrap());
+        
+        // Anyone can fund an escrow of another mint, which must not release the vote
+        let fake_mint = Pubkey::new_unique();
+        let mut mint_data = vec![0; Mint::LEN];
+        let mint_state = Mint { decimals: 6, is_initialized: true, ..Mint::default() };
+        Mint::pack(mint_state, &mut mint_data).unwrap();
+        let mut fake_mint_account = TestAccount::new(fake_mint, spl_token::id(), mint_data);
+        let mut fake_escrow = token_test_account(&vote_key, &fake_mint, 10);
+        let mut voter_fake_tokens = token_test_account(&voter.key, &fake_mint, 0);
+        let mut release = |token_program: &mut TestAccount| {
+            let accounts = [
+                voter.info(false, true),
+                vote_account.info(false, true),
+                proposal.info(false, false),
+                fake_escrow.info(false, true),
+                voter_fake_tokens.info(false, true),
+                fake_mint_account.info(false, false),
+                token_program.info(false, false),
+            ];
+            process_release_vote_tokens(&program_id, &accounts)
+        };
+        let mut token_program = TestAccount::new(spl_token::id(), Pubkey::default(), vec![]);
+        assert_eq!(release(&mut token_program), Err(DaoError::InvalidTokenAccount.into()));
+        let mut other_program = TestAccount::new(Pubkey::new_unique(), Pubkey::default(), vec![]);
+        assert_eq!(release(&mut other_program), Err(ProgramError::IncorrectProgramId));
+        assert_eq!(token_balance(&fake_escrow), 10);
+        let vote = Vote::deserialize(&mut &vote_account.data[..]).unwrap();
+        assert_eq!((vote.locked_amount, vote.locked_mint), (10, Some(mint)));
+    }
+    
+    #[test]
+    fn test_vote_with_foreign_vote_account_is_rejected() {
+        let program_id = Pubkey::new_unique();
+        let voter = Pubkey::new_unique();
+        let mut proposal = proposal_test_account(program_id);
+        
+        // A fresh keypair instead of the (proposal, voter) PDA
+        let mut vote_account = TestAccount::new(Pubkey::new_unique(), system_program::id(), vec![]);
+        
+        let proposal_id = proposal.key.to_string();
+        let result =
+            vote_ix_result(&program_id, voter, &mut vote_account, &mut proposal, proposal_id);
+        assert_eq!(result, Err(DaoError::InvalidAccountAddress.into()));
+    }
+    
+    #[test]
+    fn test_vote_on_foreign_or_mismatched_proposal_is_rejected() {
+        let program_id = Pubkey::new_unique();
+        let voter = Pubkey::new_unique();
+        
+        // Proposal account owned by another program
+        let mut proposal = proposal_test_account(Pubkey::new_unique());
+        let (vote_pubkey, _) = pda::find_vote_address(&program_id, &proposal.key, &voter);
+        let mut vote_account = TestAccount::new(vote_pubkey, system_program::id(), vec![]);
+        let proposal_id = proposal.key.to_string();
+        let result =
+            vote_ix_result(&program_id, voter, &mut vote_account, &mut proposal, proposal_id);
+        assert_eq!(result, Err(DaoError::InvalidAccountOwner.into()));
+        
+        // proposal_id pointing at a different proposal
+        let mut proposal = proposal_test_account(program_id);
+        let (vote_pubkey, _) = pda::find_vote_address(&program_id, &proposal.key, &voter);
+        let mut vote_account = TestAccount::new(vote_pubkey, system_program::id(), vec![]);
+        let proposal_id = Pubkey::new_unique().to_string();
+        let result =
+            vote_ix_result(&program_id, voter, &mut vote_account, &mut proposal, proposal_id);
+        assert_eq!(result, Err(DaoError::ProposalMismatch.into()));
+    }
+    
+    #[test]
+    fn test_create_proposal_requires_existing_dao() {
+        let program_id = Pubkey::new_unique();
+        
+        // DAO id that doesn't match the DAO account
+        let dao_data = test_dao().try_to_vec().unwrap();
+        let mut dao = TestAccount::new(Pubkey::new_unique(), program_id, dao_data);
+        let result = create_proposal_ix_result(&program_id, &mut dao, Pubkey::new_unique());
+        assert_eq!(result, Err(DaoError::DaoMismatch.into()));
+        
+        // DAO account that was never created
+        let mut dao = TestAccount::new(Pubkey::new_unique(), system_program::id(), vec![]);
+        let dao_id = dao.key;
+        let result = create_proposal_ix_result(&program_id, &mut dao, dao_id);
+        assert_eq!(result, Err(DaoError::InvalidAccountOwner.into()));
+        
+        // Another program account passed off as a DAO
+        let mut dao = proposal_test_account(program_id);
+        let dao_id = dao.key;
+        let result = create_proposal_ix_result(&program_id, &mut dao, dao_id);
+        assert_eq!(result, Err(DaoError::InvalidAccountType.into()));
+    }
+    
+    #[test]
+    fn test_token_vote_weight() {
+        let voter = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let token_account_data = |mint: Pubkey, owner: Pubkey, amount: u64| {
+            let mut data = vec![0; TokenAccount::LEN];
+            let token_account = TokenAccount {
+                mint,
+                owner,
+                amount,
+                state: spl_token::state::AccountState::Initialized,
+                ..TokenAccount::default()
+            };
+            TokenAccount::pack(token_account, &mut data).unwrap();
+            data
+        };
+        let ata = get_associated_token_address(&voter, &mint);
+        
+        // Weight is the voter's token balance
+        let data = token_account_data(mint, voter, 42);
+        let mut token_account = TestAccount::new(ata, spl_token::id(), data);
+        assert_eq!(token_vote_weight(&voter, &mint, &token_account.info(false, false)), Ok(42));
+        
+        // Holding no tokens gives no voting power
+        let data = token_account_data(mint, voter, 0);
+        let mut token_account = TestAccount::new(ata, spl_token::id(), data);
+        assert_eq!(
+            token_vote_weight(&voter, &mint, &token_account.info(false, false)),
+            Err(DaoError::NoVotingPower.into())
+        );
+        
+        // Someone else's token account can't be used
+        let other = Pubkey::new_unique();
+        let other_ata = get_associated_token_address(&other, &mint);
+        let data = token_account_data(mint, other, 42);
+        let mut token_account = TestAccount::new(other_ata, spl_token::id(), data);
+        assert_eq!(
+            token_vote_weight(&voter, &mint, &token_account.info(false, false)),
+            Err(DaoError::InvalidTokenAccount.into())
+        );
+        
+        // Token account data not owned by the token program
+        let data = token_account_data(mint, voter, 42);
+        let mut token_account = TestAccount::new(ata, Pubkey::new_unique(), data);
+        assert_eq!(
+            token_vote_weight(&voter, &mint, &token_account.info(false, false)),
+            Err(DaoError::InvalidAccountOwner.into())
+        );
+    }
+    
+    #[test]
+    fn test_proposal_outcome() {
+        let governance = test_dao().governance;
+        let outcome = |weight_for, weight_against| {
+            let mut proposal = test_proposal();
+            proposal.options[0].weight = weight_for;
+            proposal.options[1].weight = weight_against;
+            proposal.outcome(&governance)
+        };
+        
+        // Quorum of 10 not reached
+        assert_eq!(outcome(9, 0), None);
+        // 60% approval threshold
+        assert_eq!(outcome(6, 4), Some(0));
+        assert_eq!(outcome(59, 41), None);
+        assert_eq!(outcome(u64::MAX, u64::MAX / 2), Some(0));
+        
+        // A proposal nobody voted for never passes
+        let governance = GovernanceConfig {
+            quorum: 0,
+            approval_threshold_bps: 1,
+            execution_delay: 0,
+            voting_mode: VotingMode::Token,
+        };
+        assert_eq!(test_proposal().outcome(&governance), None);
+    }
+    
+    #[test]
+    fn test_multiple_choice_outcome() {
+        let governance = test_dao().governance;
+        let labels = ["A", "B", "C"].map(String::from).to_vec();
+        let outcome = |weights: [u64; 3], weight_abstain| {
+            let mut proposal = test_proposal();
+            (proposal.kind, proposal.options) = proposal_options(labels.clone(), false).unwrap();
+            for (option, weight) in proposal.options.iter_mut().zip(weights) {
+                option.weight = weight;
+            }
+            proposal.weight_abstain = weight_abstain;
+            (proposal.outcome(&governance), proposal.approved_option(&governance))
+        };
+        
+        // The leading option must reach the 60% approval threshold
+        assert_eq!(outcome([2, 6, 2], 0), (Some(1), Some(1)));
+        assert_eq!(outcome([3, 5, 2], 0), (None, None));
+        // Ties have no winner
+        assert_eq!(outcome([5, 5, 0], 0), (None, None));
+        // Abstentions count toward the quorum of 10, but not the approval threshold
+        assert_eq!(outcome([0, 6, 0], 3), (None, Some(1)));
+        assert_eq!(outcome([0, 6, 0], 4), (Some(1), Some(1)));
+        
+        // Options are bounded, non-empty and unique
+        assert!(proposal_options(vec!["A".to_string()], false).is_err());
+        assert!(proposal_options(vec![String::new(), "B".to_string()], false).is_err());
+        assert!(proposal_options(vec!["A".to_string(), "A".to_string()], false).is_err());
+        assert!(proposal_options(vec!["A".to_string(); MAX_PROPOSAL_OPTIONS + 1], false).is_err());
+        assert!(proposal_options(vec!["A".repeat(MAX_OPTION_LABEL_LEN + 1), "B".to_string()], false)
+            .is_err());
+    }
     
-    // Verify the creator is signer
-    if !creator_account.is_signer {
-        return Err(ProgramError::MissingRequiredSignature);
+    #[test]
+    fn test_ranked_choice_runoff() {
+        let labels = ["A", "B", "C"].map(String::from).to_vec();
+        let mut proposal = test_proposal();
+        (proposal.kind, proposal.options) = proposal_options(labels, true).unwrap();
+        proposal.runoff = Some(Runoff::new(3));
+        
+        // First preferences: A 4, B 3, C 3
+        for (ranking, weight) in [(vec![0, 2], 4), (vec![1], 3), (vec![2, 1], 2), (vec![2, 0], 1)] {
+            proposal.record_vote(&VoteChoice::Ranking(ranking), weight).unwrap();
+        }
+        assert_eq!(proposal.options[0].weight, 4);
+        assert_eq!(proposal.runoff.as_ref().unwrap().tallies, vec![4, 3, 3]);
+        
+        // No majority: C is eliminated, losing the tie with B
+        let mut runoff = proposal.runoff.clone().unwrap();
+        assert_eq!(runoff.resolve(), RunoffResult::NextRound);
+        assert!(runoff.is_eliminated(2) && runoff.round == 2);
+        
+        // C's ballots move on: A 5, B 5, so B is eliminated
+        for (ranking, weight) in [(vec![0, 2], 4), (vec![1], 3), (vec![2, 1], 2), (vec![2, 0], 1)] {
+            runoff.count(&ranking, weight).unwrap();
+        }
+        assert_eq!(runoff.tallies, vec![5, 5, 0]);
+        assert_eq!(runoff.resolve(), RunoffResult::NextRound);
+        
+        // B's ballots are exhausted, leaving A with all 5 continuing ballots
+        for (ranking, weight) in [(vec![0, 2], 4), (vec![1], 3), (vec![2, 1], 2), (vec![2, 0], 1)] {
+            runoff.count(&ranking, weight).unwrap();
+        }
+        assert_eq!(runoff.exhausted_weight, 5);
+        assert_eq!(runoff.resolve(), RunoffResult::Winner(0));
+        
+        // Rounds where every ballot is exhausted have no winner
+        let mut runoff = Runoff::new(2);
+        runoff.eliminated = 0b01;
+        runoff.count(&[0], 1).unwrap();
+        assert_eq!(runoff.resolve(), RunoffResult::NoWinner);
+        
+        // Rankings list distinct options of a ranked choice proposal
+        for ranking in [vec![], vec![0, 0], vec![3], vec![0, 1, 2, 0]] {
+            assert_eq!(
+                proposal.record_vote(&VoteChoice::Ranking(ranking), 1),
+                Err(DaoError::InvalidVote.into())
+            );
+        }
+        assert_eq!(
+            proposal.record_vote(&VoteChoice::Option(0), 1),
+            Err(DaoError::InvalidVote.into())
+        );
+        assert_eq!(
+            test_proposal().record_vote(&VoteChoice::Ranking(vec![0]), 1),
+            Err(DaoError::InvalidVote.into())
+        );
+        assert!(proposal_options(vec![], true).is_err());
     }
     
-    // Verify fee address is correct
-    let expected_fee_pubkey = Pubkey::from_str(FEE_ADDRESS).unwrap();
-    if *fee_account.key != expected_fee_pubkey {
-        return Err(DaoError::InvalidFeeAccount.into());
+    #[test]
+    fn test_count_ranked_votes() {
+        setup_syscall_stubs();
+        let program_id = Pubkey::new_unique();
+        let dao = test_dao();
+        let dao_data = dao.try_to_vec().unwrap();
+        let mut dao_account = TestAccount::new(Pubkey::new_unique(), program_id, dao_data);
+        let labels = ["A", "B", "C"].map(String::from).to_vec();
+        let mut proposal = test_proposal();
+        proposal.dao_id = dao_account.key;
+        proposal.end_time = TEST_NOW - 1;
+        (proposal.kind, proposal.options) = proposal_options(labels, true).unwrap();
+        proposal.runoff = Some(Runoff::new(3));
+        let proposal_key = Pubkey::new_unique();
+        
+        // Cast the ballots, counted in the first round as they are recorded
+        let ballots = [(vec![0, 2], 4), (vec![1], 3), (vec![2, 1], 2), (vec![2, 0], 1)];
+        let mut votes: Vec<TestAccount> = ballots
+            .into_iter()
+            .map(|(ranking, weight)| {
+                let voter = Pubkey::new_unique();
+                let choice = VoteChoice::Ranking(ranking);
+                proposal.record_vote(&choice, weight).unwrap();
+                let vote = Vote {
+                    account_type: AccountType::Vote,
+                    voter,
+                    choice,
+                    proposal_id: proposal_key.to_string(),
+                    weight,
+                    counted_round: 1,
+                    credits_spent: 0,
+                    locked_amount: 0,
+                    locked_mint: None,
+                };
+                let (vote_key, _) = pda::find_vote_address(&program_id, &proposal_key, &voter);
+                TestAccount::new(vote_key, program_id, vote.try_to_vec().unwrap())
+            })
+            .collect();
+        let mut data = proposal.try_to_vec().unwrap();
+        data.resize(proposal.space().unwrap(), 0);
+        let mut proposal_account = TestAccount::new(proposal_key, program_id, data);
+        
+        // Counting needs a finalized proposal without a first round majority
+        {
+            let accounts = [proposal_account.info(false, true), dao_account.info(false, false)];
+            assert_eq!(
+                process_count_ranked_votes(&program_id, &accounts),
+                Err(DaoError::ProposalNotTallying.into())
+            );
+            process_finalize_proposal(&program_id, &accounts).unwrap();
+        }
+        let mut count = |votes: &mut [TestAccount]| {
+            let mut accounts =
+                vec![proposal_account.info(false, true), dao_account.info(false, false)];
+            accounts.extend(votes.iter_mut().map(|vote| vote.info(false, true)));
+            let result = process_count_ranked_votes(&program_id, &accounts);
+            let proposal = Proposal::deserialize(&mut &accounts[0].data.borrow()[..]).unwrap();
+            result.map(|_| (proposal.state, proposal.runoff.unwrap().round))
+        };
+        
+        // Round 2 is counted across two calls, ballots being counted once per round
+        assert_eq!(count(&mut votes[..2]), Ok((ProposalState::Tallying, 2)));
+        assert_eq!(count(&mut votes[..2]), Ok((ProposalState::Tallying, 2)));
+        assert_eq!(count(&mut votes), Ok((ProposalState::Tallying, 3)));
+        
+        // Votes of other proposals are rejected
+        let mut other_vote = TestAccount::new(Pubkey::new_unique(), program_id, vec![]);
+        other_vote.data = votes[0].data.clone();
+        assert_eq!(
+            count(std::slice::from_mut(&mut other_vote)),
+            Err(DaoError::InvalidAccountAddress.into())
+        );
+        
+        // Round 3 elects A
+        assert_eq!(count(&mut votes), Ok((ProposalState::Queued, 3)));
+        let finalized = Proposal::try_from_slice(&proposal_account.data).unwrap();
+        assert_eq!(finalized.winning_option, Some(0));
+        assert_eq!(finalized.executable_at, TEST_NOW + dao.governance.execution_delay);
+        let vote = Vote::try_from_slice(&votes[3].data).unwrap();
+        assert_eq!(vote.counted_round, 3);
     }
     
-    // Calculate fee based on current SOL price and number of days
-    let feature_fee = calculate_featured_fee_in_lamports(sol_price_usd, days)?;
-    let total_usd_fee = CREATE_DAO_FEE_USD * days;
+    #[test]
+    fn test_governance_config_validate() {
+        let config = |approval_threshold_bps, execution_delay| GovernanceConfig {
+            quorum: 0,
+            approval_threshold_bps,
+            execution_delay,
+            voting_mode: VotingMode::Token,
+        };
+        assert!(config(5_000, 0).validate().is_ok());
+        assert!(config(10_000, 86_400).validate().is_ok());
+        assert_eq!(config(0, 0).validate(), Err(DaoError::InvalidGovernanceConfig.into()));
+        assert_eq!(config(10_001, 0).validate(), Err(DaoError::InvalidGovernanceConfig.into()));
+        assert_eq!(config(5_000, -1).validate(), Err(DaoError::InvalidGovernanceConfig.into()));
+        
+        // Reputation weights can be capped, but not to nothing
+        let mut capped = config(5_000, 0);
+        capped.voting_mode = VotingMode::Reputation { max_weight: Some(1) };
+        assert!(capped.validate().is_ok());
+        capped.voting_mode = VotingMode::Reputation { max_weight: Some(0) };
+        assert_eq!(capped.validate(), Err(DaoError::InvalidGovernanceConfig.into()));
+    }
     
-    msg!("Featured DAO fee: {} lamports (${} for {} days at SOL price of ${}.{})", 
-        feature_fee,
-        total_usd_fee,
-        days,
-        sol_price_usd / 100,
-        sol_price_usd % 100
-    );
+    #[test]
+    fn test_execute_proposal_requires_passed_proposal_and_recipient() {
+        setup_syscall_stubs();
+        let program_id = Pubkey::new_unique();
+        let recipient = Pubkey::new_unique();
+        let mut proposal = test_proposal();
+        proposal.withdrawal = Some(TreasuryWithdrawal {
+            recipient,
+            mint: None,
+            amount: 1_000_000,
+        });
+        
+        // Only queued proposals can be executed
+        for state in [
+            ProposalState::Voting,
+            ProposalState::Rejected,
+            ProposalState::Executed,
+            ProposalState::Cancelled,
+        ] {
+            proposal.state = state;
+            let result = execute_proposal_ix_result(&program_id, &proposal, recipient);
+            assert_eq!(result, Err(DaoError::ProposalNotPassed.into()));
+        }
+        
+        // Queued proposals wait for the execution delay
+        proposal.state = ProposalState::Queued;
+        proposal.executable_at = TEST_NOW + 1;
+        let result = execute_proposal_ix_result(&program_id, &proposal, recipient);
+        assert_eq!(result, Err(DaoError::TimelockNotExpired.into()));
+        
+        // Funds can only go to the recipient named in the proposal
+        proposal.executable_at = TEST_NOW;
+        let result = execute_proposal_ix_result(&program_id, &proposal, Pubkey::new_unique());
+        assert_eq!(result, Err(DaoError::InvalidWithdrawal.into()));
+    }
     
-    // Create featured data structure
-    let featured_data = Featured {
-        authority: *creator_account.key,
-        dao_id,
-        days,
-    };
+    #[test]
+    fn test_check_proposal_instructions() {
+        let program_id = Pubkey::new_unique();
+        let treasury = Pubkey::new_unique();
+        let instruction = |program_id, signer| ProposalInstruction {
+            program_id,
+            accounts: vec![ProposalAccountMeta {
+                pubkey: signer,
+                is_signer: true,
+                is_writable: true,
+            }],
+            data: vec![],
+            executed: false,
+        };
+        let other_program = Pubkey::new_unique();
+        let check = |instructions: &[ProposalInstruction]| {
+            check_proposal_instructions(&program_id, &treasury, instructions)
+        };
+        let invalid = Err(DaoError::InvalidProposalInstruction.into());
+        
+        // Only the treasury can sign, and never for this program
+        assert_eq!(check(&[instruction(other_program, treasury)]), Ok(()));
+        assert_eq!(check(&[instruction(other_program, Pubkey::new_unique())]), invalid);
+        assert_eq!(check(&[instruction(program_id, treasury)]), invalid);
+        
+        // Bounded number of instructions
+        let instructions =
+            vec![instruction(other_program, treasury); MAX_PROPOSAL_INSTRUCTIONS + 1];
+        assert_eq!(check(&instructions), invalid);
+    }
     
-    // Calculate space required for the featured account
-    let featured_serialized = featured_data.try_to_vec()?;
-    let space = featured_serialized.len() as u64;
+    #[test]
+    fn test_finalize_proposal_queues_passed_proposal() {
+        setup_syscall_stubs();
+        let program_id = Pubkey::new_unique();
+        let dao = test_dao();
+        let mut proposal = test_proposal();
+        let dao_data = dao.try_to_vec().unwrap();
+        let mut dao_account = TestAccount::new(proposal.dao_id, program_id, dao_data);
+        
+        // Voting is still open
+        let data = proposal.try_to_vec().unwrap();
+        let mut proposal_account = TestAccount::new(Pubkey::new_unique(), program_id, data);
+        let accounts = [proposal_account.info(false, true), dao_account.info(false, false)];
+        assert_eq!(
+            process_finalize_proposal(&program_id, &accounts),
+            Err(DaoError::VotingNotEnded.into())
+        );
+        
+        // Voting is over and the proposal passed
+        proposal.end_time = TEST_NOW - 1;
+        proposal.options[0].weight = 10;
+        let mut data = proposal.try_to_vec().unwrap();
+        data.resize(proposal.space().unwrap(), 0);
+        let mut proposal_account = TestAccount::new(Pubkey::new_unique(), program_id, data);
+        let accounts = [proposal_account.info(false, true), dao_account.info(false, false)];
+        assert_eq!(process_finalize_proposal(&program_id, &accounts), Ok(()));
+        
+        let finalized = Proposal::try_from_slice(&proposal_account.data).unwrap();
+        assert_eq!(finalized.state, ProposalState::Queued);
+        assert_eq!(finalized.finalized_at, TEST_NOW);
+        assert_eq!(finalized.executable_at, TEST_NOW + dao.governance.execution_delay);
+        assert_eq!(finalized.winning_option, Some(0));
+    }
     
-    // Calculate the rent required
-    let rent = Rent::get()?;
-    let rent_lamports = rent.minimum_balance(space as usize);
+    #[test]
+    fn test_cancel_proposal_during_execution_delay() {
+        setup_syscall_stubs();
+        let program_id = Pubkey::new_unique();
+        let dao = test_dao();
+        let mut proposal = test_proposal();
+        proposal.state = ProposalState::Queued;
+        proposal.executable_at = TEST_NOW + 1;
+        let dao_data = dao.try_to_vec().unwrap();
+        let mut dao_account = TestAccount::new(proposal.dao_id, program_id, dao_data);
+        let cancel = |authority: Pubkey, proposal: &Proposal, dao_account: &mut TestAccount| {
+            let data = proposal.try_to_vec().unwrap();
+            let mut proposal_account = TestAccount::new(Pubkey::new_unique(), program_id, data);
+            let mut authority = TestAccount::new(authority, system_program::id(), vec![]);
+            let accounts = [
+                authority.info(true, false),
+                proposal_account.info(false, true),
+                dao_account.info(false, false),
+            ];
+            process_cancel_proposal(&program_id, &accounts)
+                .map(|_| Proposal::try_from_slice(&proposal_account.data).unwrap().state)
+        };
+        
+        // Only the DAO authority can cancel
+        let result = cancel(Pubkey::new_unique(), &proposal, &mut dao_account);
+        assert_eq!(result, Err(DaoError::Unauthorized.into()));
+        
+        // Cancelling during the delay
+        let result = cancel(dao.authority, &proposal, &mut dao_account);
+        assert_eq!(result, Ok(ProposalState::Cancelled));
+        
+        // Too late once the delay has elapsed
+        proposal.executable_at = TEST_NOW;
+        let result = cancel(dao.authority, &proposal, &mut dao_account);
+        assert_eq!(result, Err(DaoError::TimelockExpired.into()));
+        
+        // Only queued proposals can be cancelled
+        proposal.state = ProposalState::Voting;
+        let result = cancel(dao.authority, &proposal, &mut dao_account);
+        assert_eq!(result, Err(DaoError::ProposalNotQueued.into()));
+    }
     
-    // Check if creator has enough funds for rent + fee
-    if creator_account.lamports() < rent_lamports + feature_fee {
-        return Err(DaoError::InsufficientFunds.into());
+    #[test]
+    fn test_sol_price_from_oracle() {
+        // $150.00 with a $0.10 confidence interval, published 10 seconds ago
+        let price = oracle::PythPrice {
+            price: 15_000_000_000,
+            conf: 10_000_000,
+            expo: -8,
+            publish_time: TEST_NOW - 10,
+        };
+        let read_price = |price: &oracle::PythPrice| {
+            let (key, owner, data) = oracle::mock_sol_usd_price_account(price);
+            let mut price_account = TestAccount::new(key, owner, data);
+            oracle::sol_price_usd_cents(&price_account.info(false, false), TEST_NOW)
+        };
+        assert_eq!(read_price(&price), Ok(15_000));
+        
+        // Price older than the maximum age
+        let stale = oracle::PythPrice {
+            publish_time: TEST_NOW - oracle::MAX_PRICE_AGE_SECS - 1,
+            ..price
+        };
+        assert_eq!(read_price(&stale), Err(DaoError::StalePrice.into()));
+        
+        // Confidence interval wider than 2% of the price
+        let uncertain = oracle::PythPrice {
+            conf: 400_000_000,
+            ..price
+        };
+        assert_eq!(read_price(&uncertain), Err(DaoError::PriceConfidenceTooWide.into()));
+        
+        // Price accounts other than the SOL/USD feed are rejected
+        let (key, owner, data) = oracle::mock_sol_usd_price_account(&price);
+        let mut fake_feed = TestAccount::new(key, Pubkey::new_unique(), data.clone());
+        assert_eq!(
+            oracle::sol_price_usd_cents(&fake_feed.info(false, false), TEST_NOW),
+            Err(DaoError::InvalidPriceAccount.into())
+        );
+        let mut other_feed = TestAccount::new(Pubkey::new_unique(), owner, data);
+        assert_eq!(
+            oracle::sol_price_usd_cents(&other_feed.info(false, false), TEST_NOW),
+            Err(DaoError::InvalidPriceAccount.into())
+        );
     }
     
-    // Create the featured account
-    invoke(
-        &system_instruction::create_account(
-            creator_account.key,
-            featured_account.key,
-            rent_lamports,
-            space,
-            program_id,
-        ),
-        &[creator_account.clone(), featured_account.clone(), system_program.clone()],
-    )?;
+    #[test]
+    fn test_initialize_config_requires_upgrade_authority() {
+        let program_id = Pubkey::new_unique();
+        let upgrade_authority = Pubkey::new_unique();
+        
+        // ProgramData account recording the upgrade authority
+        let (program_data, _) =
+            Pubkey::find_program_address(&[program_id.as_ref()], &bpf_loader_upgradeable::id());
+        let mut data = vec![0; UpgradeableLoaderState::size_of_programdata_metadata()];
+        data[0..4].copy_from_slice(&3u32.to_le_bytes());
+        data[12] = 1;
+        data[13..45].copy_from_slice(upgrade_authority.as_ref());
+        let mut program_data = TestAccount::new(program_data, bpf_loader_upgradeable::id(), data);
+        
+        let mut config = TestAccount::new(
+            pda::find_config_address(&program_id).0,
+            system_program::id(),
+            vec![],
+        );
+        let mut system = TestAccount::new(system_program::id(), Pubkey::default(), vec![]);
+        let mut impostor = TestAccount::new(Pubkey::new_unique(), system_program::id(), vec![]);
+        let accounts = [
+            impostor.info(true, true),
+            config.info(false, true),
+            program_data.info(false, false),
+            system.info(false, false),
+        ];
+        let fees = test_config().fees;
+        let result = process_initialize_config(
+            &program_id,
+            &accounts,
+            upgrade_authority,
+            upgrade_authority,
+            fees,
+            vec![],
+            test_config().fee_split,
+            test_config().featured_auction,
+            vec![],
+        );
+        assert_eq!(result, Err(DaoError::Unauthorized.into()));
+        
+        let accounts = [
+            impostor.info(true, true),
+            config.info(false, true),
+            program_data.info(false, false),
+            system.info(false, false),
+        ];
+        assert_eq!(
+            program_upgrade_authority(&program_id, &accounts[2]),
+            Ok(Some(upgrade_authority))
+        );
+        
+        // Another program's ProgramData account can't be used
+        assert_eq!(
+            program_upgrade_authority(&Pubkey::new_unique(), &accounts[2]),
+            Err(DaoError::InvalidAccountAddress.into())
+        );
+    }
     
-    // Transfer fee to fee account
-    invoke(
-        &system_instruction::transfer(
-            creator_account.key,
-            fee_account.key,
-            feature_fee,
-        ),
-        &[creator_account.clone(), fee_account.clone(), system_program.clone()],
-    )?;
+    #[test]
+    fn test_update_config_requires_admin() {
+        let program_id = Pubkey::new_unique();
+        let config_data = test_config();
+        let admin = config_data.admin;
+        let mut config = config_test_account(&program_id, &config_data);
+        let mut fees = config_data.fees;
+        fees.vote = 50;
+        let new_admin = Pubkey::new_unique();
+        let fee_recipient = Pubkey::new_unique();
+        let stable_mints = vec![Pubkey::new_unique()];
+        let mut system = TestAccount::new(system_program::id(), Pubkey::default(), vec![]);
+        
+        // Anyone else is rejected
+        let mut impostor = TestAccount::new(Pubkey::new_unique(), system_program::id(), vec![]);
+        let accounts = [
+            impostor.info(true, true),
+            config.info(false, true),
+            system.info(false, false),
+        ];
+        let result = process_update_config(
+            &program_id,
+            &accounts,
+            new_admin,
+            fee_recipient,
+            fees,
+            stable_mints.clone(),
+            config_data.fee_split,
+            config_data.featured_auction,
+            config_data.featured_tiers.clone(),
+        );
+        assert_eq!(result, Err(DaoError::Unauthorized.into()));
+        
+        // The allowlist of stablecoins is bounded
+        let mut admin = TestAccount::new(admin, system_program::id(), vec![]);
+        let accounts = [
+            admin.info(true, true),
+            config.info(false, true),
+            system.info(false, false),
+        ];
+        let result = process_update_config(
+            &program_id,
+            &accounts,
+            new_admin,
+            fee_recipient,
+            fees,
+            vec![Pubkey::new_unique(); MAX_STABLE_MINTS + 1],
+            config_data.fee_split,
+            config_data.featured_auction,
+            config_data.featured_tiers.clone(),
+        );
+        assert_eq!(result, Err(DaoError::InvalidConfig.into()));
+        
+        // The admin updates the fees, the fee recipient and hands over the config
+        let accounts = [
+            admin.info(true, true),
+            config.info(false, true),
+            system.info(false, false),
+        ];
+        process_update_config(
+            &program_id,
+            &accounts,
+            new_admin,
+            fee_recipient,
+            fees,
+            stable_mints.clone(),
+            config_data.fee_split,
+            config_data.featured_auction,
+            config_data.featured_tiers.clone(),
+        )
+        .unwrap();
+        
+        let updated = Config::try_from_slice(&config.data).unwrap();
+        assert_eq!(updated.admin, new_admin);
+        assert_eq!(updated.fee_recipient, fee_recipient);
+        assert_eq!(updated.fees.vote, 50);
+        assert_eq!(updated.stable_mints, stable_mints);
+        
+        // The previous admin lost control of the config
+        let accounts = [
+            admin.info(true, true),
+            config.info(false, true),
+            system.info(false, false),
+        ];
+        let result = process_update_config(
+            &program_id,
+            &accounts,
+            config_data.admin,
+            fee_recipient,
+            fees,
+            stable_mints,
+            config_data.fee_split,
+            config_data.featured_auction,
+            config_data.featured_tiers.clone(),
+        );
+        assert_eq!(result, Err(DaoError::Unauthorized.into()));
+        
+        // Module fees come from the updated schedule
+        assert_eq!(updated.fees.module_fee(ModuleType::Pol), fees.pol_module);
+    }
     
-    // Serialize and store the featured data
-    featured_data.serialize(&mut &mut featured_account.data.borrow_mut()[..])?;
+    #[test]
+    fn test_stable_fee_payment() {
+        setup_syscall_stubs();
+        let config = test_config();
+        let stable_mint = config.stable_mints[0];
+        
+        // The exact USD amount in the mint's base units
+        assert_eq!(calculate_fee_in_stable_tokens(2_000, 6), Ok(20_000_000));
+        assert_eq!(calculate_fee_in_stable_tokens(75, 2), Ok(75));
+        assert_eq!(calculate_fee_in_stable_tokens(2_000, 0), Err(DaoError::InvalidFeeMint.into()));
+        
+        let mint_data = |decimals: u8| {
+            let mut data = vec![0; Mint::LEN];
+            let mint = Mint { decimals, is_initialized: true, ..Mint::default() };
+            Mint::pack(mint, &mut data).unwrap();
+            data
+        };
+        let mut token_program = TestAccount::new(spl_token::id(), Pubkey::default(), vec![]);
+        let mut payer_tokens = TestAccount::new(Pubkey::new_unique(), spl_token::id(), vec![]);
+        let recipient_ata = get_associated_token_address(&config.fee_recipient, &stable_mint);
+        let mut recipient_tokens = TestAccount::new(recipient_ata, spl_token::id(), vec![]);
+        
+        // An allowlisted stablecoin paid to the fee recipient's associated token account
+        let mut mint = TestAccount::new(stable_mint, spl_token::id(), mint_data(6));
+        let accounts = [
+            recipient_tokens.info(false, true),
+            payer_tokens.info(false, true),
+            mint.info(false, false),
+            token_program.info(false, false),
+        ];
+        let fee = FeePayment::new(
+            &config,
+            2_000,
+            Some(stable_mint),
+            &accounts[0],
+            &accounts[1],
+            &mut accounts[2..].iter(),
+        )
+        .unwrap();
+        assert_eq!(fee.lamports(), 0);
+        assert_eq!(fee.to_string(), format!("20000000 tokens of mint {}", stable_mint));
+        
+        // The tokens must go to the fee recipient
+        let result = FeePayment::new(
+            &config,
+            2_000,
+            Some(stable_mint),
+            &accounts[1],
+            &accounts[1],
+            &mut accounts[2..].iter(),
+        );
+        assert_eq!(result.err(), Some(DaoError::InvalidFeeAccount.into()));
+        
+        // Mints outside the allowlist are rejected
+        let other_mint = Pubkey::new_unique();
+        let mut mint = TestAccount::new(other_mint, spl_token::id(), mint_data(6));
+        let accounts = [
+            recipient_tokens.info(false, true),
+            payer_tokens.info(false, true),
+            mint.info(false, false),
+            token_program.info(false, false),
+        ];
+        let result = FeePayment::new(
+            &config,
+            2_000,
+            Some(other_mint),
+            &accounts[0],
+            &accounts[1],
+            &mut accounts[2..].iter(),
+        );
+        assert_eq!(result.err(), Some(DaoError::InvalidFeeMint.into()));
+    }
     
-    msg!("DAO featured successfully with ID: {}", featured_account.key);
-    Ok(())
-}
-
-// Process the modules instruction
-pub fn process_modules(
-    program_id: &Pubkey,
-    accounts: &[AccountInfo],
-    dao_id: String,
-    module_type: String,
-    sol_price_usd: u64,
-) -> ProgramResult {
-    let account_info_iter = &mut accounts.iter();
+    #[test]
+    fn test_fee_split() {
+        // The share is rounded down and the platform keeps the remainder
+        assert_eq!(split_fee(1_001, 2_500), (751, 250));
+        assert_eq!(split_fee(3, 3_333), (3, 0));
+        assert_eq!(split_fee(u64::MAX, 10_000), (0, u64::MAX));
+        for amount in [0, 1, 7, 999, 123_456_789, u64::MAX] {
+            for bps in [0, 1, 1_000, 3_333, 9_999, 10_000] {
+                let (platform, share) = split_fee(amount, bps);
+                assert_eq!(platform + share, amount);
+            }
+        }
+        
+        // Shares can't exceed the whole fee
+        let mut config = test_config();
+        assert_eq!(config.validate(), Ok(()));
+        config.fee_split.treasury_rebate_bps = 10_001;
+        assert_eq!(config.validate(), Err(DaoError::InvalidConfig.into()));
+        
+        // SOL shares go to the wallet itself
+        let wallet = Pubkey::new_unique();
+        let mut fee_account = TestAccount::new(Pubkey::new_unique(), system_program::id(), vec![]);
+        let mut wallet_account = TestAccount::new(wallet, system_program::id(), vec![]);
+        let fee_account = fee_account.info(false, true);
+        let wallet_account = wallet_account.info(false, true);
+        let fee = FeePayment::Sol { fee_account: &fee_account, lamports: 100, sol_price_usd: 100 };
+        assert_eq!(fee.check_share_account(&wallet_account, &wallet), Ok(()));
+        assert_eq!(
+            fee.check_share_account(&fee_account, &wallet),
+            Err(DaoError::InvalidFeeAccount.into())
+        );
+        
+        // Stablecoin shares go to the wallet's associated token account
+        let mint = Pubkey::new_unique();
+        let mut mint_account = TestAccount::new(mint, spl_token::id(), vec![]);
+        let ata = get_associated_token_address(&wallet, &mint);
+        let mut ata_account = TestAccount::new(ata, spl_token::id(), vec![]);
+        let mint_account = mint_account.info(false, false);
+        let ata_account = ata_account.info(false, true);
+        let fee = FeePayment::Token {
+            source: &fee_account,
+            destination: &fee_account,
+            mint_account: &mint_account,
+            token_program: &fee_account,
+            amount: 100,
+        };
+        assert_eq!(fee.check_share_account(&ata_account, &wallet), Ok(()));
+        assert_eq!(
+            fee.check_share_account(&wallet_account, &wallet),
+            Err(DaoError::InvalidFeeAccount.into())
+        );
+        
+        // Stablecoin rebates create the treasury's associated token account when missing
+        setup_syscall_stubs();
+        let program_id = Pubkey::new_unique();
+        let mut dao = TestAccount::new(Pubkey::new_unique(), program_id, vec![]);
+        let (treasury, _) = pda::find_treasury_address(&program_id, &dao.key);
+        let mut treasury = TestAccount::new(treasury, system_program::id(), vec![]);
+        let rebate_key = get_associated_token_address(&treasury.key, &mint);
+        let mut rebate = TestAccount::new(rebate_key, system_program::id(), vec![]);
+        let mut token_program = TestAccount::new(spl_token::id(), Pubkey::default(), vec![]);
+        let mut ata_program =
+            TestAccount::new(spl_associated_token_account::id(), Pubkey::default(), vec![]);
+        let mut system = TestAccount::new(system_program::id(), Pubkey::default(), vec![]);
+        let dao = dao.info(false, false);
+        let treasury = treasury.info(false, false);
+        let rebate = rebate.info(false, true);
+        let token_program = token_program.info(false, false);
+        let ata_program = ata_program.info(false, false);
+        let system = system.info(false, false);
+        let fee = FeePayment::Token {
+            source: &fee_account,
+            destination: &fee_account,
+            mint_account: &mint_account,
+            token_program: &token_program,
+            amount: 100,
+        };
+        let cases = [
+            ([rebate.clone(), treasury.clone(), ata_program.clone()], Ok(rebate_key)),
+            (
+                [rebate.clone(), wallet_account.clone(), ata_program.clone()],
+                Err(DaoError::InvalidAccountAddress.into()),
+            ),
+            (
+                [rebate.clone(), treasury.clone(), token_program.clone()],
+                Err(ProgramError::IncorrectProgramId),
+            ),
+        ];
+        for (accounts, expected) in &cases {
+            let result = treasury_rebate_account(
+                &program_id,
+                &test_config(),
+                &dao,
+                &fee,
+                &wallet_account,
+                &system,
+                &mut accounts.iter(),
+            );
+            assert_eq!(result.map(|account| *account.key), *expected);
+        }
+    }
     
-    // Get the required accounts
-    let creator_account = next_account_info(account_info_iter)?;
-    let module_account = next_account_info(account_info_iter)?;
-    let dao_account = next_account_info(account_info_iter)?;
-    let system_program = next_account_info(account_info_iter)?;
-    let fee_account = next_account_info(account_info_iter)?;
+    #[test]
+    fn test_featured_expiry_and_renewal() {
+        let mut featured = Featured {
+            account_type: AccountType::Featured,
+            authority: Pubkey::new_unique(),
+            dao_id: Pubkey::new_unique(),
+            days: 0,
+            starts_at: 0,
+            expires_at: 0,
+        };
+        
+        // A new listing starts now
+        featured.extend(1_000, 2).unwrap();
+        assert_eq!((featured.starts_at, featured.expires_at), (1_000, 1_000 + 2 * SECONDS_PER_DAY));
+        assert!(featured.is_active(1_000));
+        assert!(!featured.is_active(featured.expires_at));
+        
+        // Renewing an active listing extends it
+        featured.extend(2_000, 1).unwrap();
+        assert_eq!((featured.starts_at, featured.expires_at), (1_000, 1_000 + 3 * SECONDS_PER_DAY));
+        assert_eq!(featured.days, 3);
+        
+        // Renewing an expired listing restarts it
+        let now = featured.expires_at + 10;
+        featured.extend(now, 1).unwrap();
+        assert_eq!((featured.starts_at, featured.expires_at), (now, now + SECONDS_PER_DAY));
+        assert_eq!(featured.extend(now, u64::MAX), Err(ProgramError::ArithmeticOverflow));
+    }
     
-    // Verify the creator is signer
-    if !creator_account.is_signer {
-        return Err(ProgramError::MissingRequiredSignature);
+    #[test]
+    fn test_close_expired_featured() {
+        setup_syscall_stubs();
+        let program_id = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+        let featured_data = |expires_at: i64| {
+            Featured {
+                account_type: AccountType::Featured,
+                authority,
+                dao_id: Pubkey::new_unique(),
+                days: 1,
+                starts_at: expires_at - SECONDS_PER_DAY,
+                expires_at,
+            }
+            .try_to_vec()
+            .unwrap()
+        };
+        let mut authority = TestAccount::new(authority, system_program::id(), vec![]);
+        let mut impostor = TestAccount::new(Pubkey::new_unique(), system_program::id(), vec![]);
+        
+        // Active listings can't be closed
+        let mut featured = TestAccount::new(Pubkey::new_unique(), program_id, featured_data(151));
+        featured.lamports = 1_000;
+        let accounts = [featured.info(false, true), authority.info(false, true)];
+        let result = process_close_expired_featured(&program_id, &accounts);
+        assert_eq!(result, Err(DaoError::FeaturedNotExpired.into()));
+        
+        // Rent only goes back to the listing's authority
+        let mut featured = TestAccount::new(Pubkey::new_unique(), program_id, featured_data(150));
+        featured.lamports = 1_000;
+        let accounts = [featured.info(false, true), impostor.info(false, true)];
+        let result = process_close_expired_featured(&program_id, &accounts);
+        assert_eq!(result, Err(DaoError::Unauthorized.into()));
+        
+        let accounts = [featured.info(false, true), authority.info(false, true)];
+        process_close_expired_featured(&program_id, &accounts).unwrap();
+        assert_eq!((featured.lamports, authority.lamports), (0, 1_000));
+        assert!(featured.data.iter().all(|byte| *byte == 0));
     }
     
-    // Verify fee address is correct
-    let expected_fee_pubkey = Pubkey::from_str(FEE_ADDRESS).unwrap();
-    if *fee_account.key != expected_fee_pubkey {
-        return Err(DaoError::InvalidFeeAccount.into());
+    #[test]
+    fn test_featured_auction_ranking() {
+        let config = FeaturedAuctionConfig { slots: 2, period_days: 1 };
+        
+        // Bidding is only open for the next period
+        assert_eq!(config.period_at(SECONDS_PER_DAY + 1), Ok(1));
+        assert_eq!(
+            FeaturedAuction::open(&config, 1, Pubkey::new_unique(), SECONDS_PER_DAY).err(),
+            Some(DaoError::AuctionClosed.into())
+        );
+        let mut auction =
+            FeaturedAuction::open(&config, 2, Pubkey::new_unique(), SECONDS_PER_DAY).unwrap();
+        assert_eq!(
+            (auction.starts_at, auction.ends_at),
+            (2 * SECONDS_PER_DAY, 3 * SECONDS_PER_DAY)
+        );
+        
+        // The account fits a bid in every slot
+        let space = auction.max_space();
+        let first = Pubkey::new_unique();
+        let second = Pubkey::new_unique();
+        let third = Pubkey::new_unique();
+        auction.place_bid(first, 100).unwrap();
+        auction.place_bid(second, 100).unwrap();
+        assert_eq!(auction.try_to_vec().unwrap().len(), space);
+        
+        // Ties go to the earlier bid, and a higher bid takes the last slot
+        assert_eq!(auction.place_bid(third, 100), Err(DaoError::BidTooLow.into()));
+        auction.place_bid(third, 101).unwrap();
+        assert_eq!(
+            auction.leaders,
+            vec![
+                FeaturedBidEntry { dao_id: third, amount: 101 },
+                FeaturedBidEntry { dao_id: first, amount: 100 },
+            ]
+        );
+        assert!(!auction.is_winning(&second));
+        
+        // Outbid DAOs can raise their bid to win a slot back
+        auction.place_bid(second, 150).unwrap();
+        assert_eq!(auction.leaders[0], FeaturedBidEntry { dao_id: second, amount: 150 });
+        assert!(!auction.is_winning(&first));
+        
+        // Only a bounded number of slots can be auctioned
+        let mut config = test_config();
+        config.featured_auction.slots = MAX_FEATURED_SLOTS + 1;
+        assert_eq!(config.validate(), Err(DaoError::InvalidConfig.into()));
     }
     
-    // Calculate fee based on current SOL price
-    let module_fee = calculate_fee_in_lamports(sol_price_usd)?;
+    #[test]
+    fn test_featured_bid_settlement() {
+        setup_syscall_stubs();
+        let program_id = Pubkey::new_unique();
+        let (winner, loser) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let bidder = Pubkey::new_unique();
+        let auction_data = FeaturedAuction {
+            account_type: AccountType::FeaturedAuction,
+            period: 1,
+            slots: 1,
+            days: 1,
+            starts_at: 100,
+            ends_at: 100 + SECONDS_PER_DAY,
+            settled: false,
+            opener: bidder,
+            open_bids: 2,
+            leaders: vec![FeaturedBidEntry { dao_id: winner, amount: 500 }],
+        };
+        let mut auction = TestAccount::new(
+            Pubkey::new_unique(),
+            program_id,
+            auction_data.try_to_vec().unwrap(),
+        );
+        auction.lamports = 50;
+        let bid_data = |dao_id: Pubkey| {
+            FeaturedBid {
+                account_type: AccountType::FeaturedBid,
+                auction: auction.key,
+                dao_id,
+                bidder,
+                amount: 500,
+            }
+            .try_to_vec()
+            .unwrap()
+        };
+        let mut winning_bid = TestAccount::new(Pubkey::new_unique(), program_id, bid_data(winner));
+        winning_bid.lamports = 600;
+        let mut losing_bid = TestAccount::new(Pubkey::new_unique(), program_id, bid_data(loser));
+        losing_bid.lamports = 600;
+        let mut bidder = TestAccount::new(bidder, system_program::id(), vec![]);
+        
+        // Winning bids stay escrowed, outbid ones are refunded with their rent
+        let accounts = [
+            bidder.info(false, true),
+            auction.info(false, true),
+            winning_bid.info(false, true),
+        ];
+        let result = process_refund_featured_bid(&program_id, &accounts);
+        assert_eq!(result, Err(DaoError::BidNotRefundable.into()));
+        let accounts = [
+            bidder.info(false, true),
+            auction.info(false, true),
+            losing_bid.info(false, true),
+        ];
+        process_refund_featured_bid(&program_id, &accounts).unwrap();
+        assert_eq!((losing_bid.lamports, bidder.lamports), (0, 600));
+        
+        // Slots can only be claimed once the auction is settled
+        let mut dao = TestAccount::new(winner, program_id, test_dao().try_to_vec().unwrap());
+        let (treasury, _) = pda::find_treasury_address(&program_id, &winner);
+        let mut treasury = TestAccount::new(treasury, system_program::id(), vec![]);
+        let config_data = test_config();
+        let mut config = config_test_account(&program_id, &config_data);
+        let mut fee_recipient =
+            TestAccount::new(config_data.fee_recipient, system_program::id(), vec![]);
+        let (featured, _) = pda::find_featured_address(&program_id, &winner);
+        let featured_data = Featured {
+            account_type: AccountType::Featured,
+            authority: bidder.key,
+            dao_id: winner,
+            days: 1,
+            starts_at: 90 - SECONDS_PER_DAY,
+            expires_at: 90,
+        };
+        let mut featured =
+            TestAccount::new(featured, program_id, featured_data.try_to_vec().unwrap());
+        let mut system = TestAccount::new(system_program::id(), Pubkey::default(), vec![]);
+        let result = {
+            let accounts = [
+                bidder.info(true, true),
+                auction.info(false, true),
+                winning_bid.info(false, true),
+                featured.info(false, true),
+                dao.info(false, false),
+                system.info(false, false),
+                config.info(false, false),
+                fee_recipient.info(false, true),
+                treasury.info(false, true),
+            ];
+            process_claim_featured_slot(&program_id, &accounts)
+        };
+        assert_eq!(result, Err(DaoError::AuctionNotSettled.into()));
+        
+        let accounts = [auction.info(false, true)];
+        process_settle_featured_auction(&program_id, &accounts).unwrap();
+        
+        // The auction can't be closed while the winning bid is escrowed
+        let accounts = [auction.info(false, true), bidder.info(false, true)];
+        let result = process_close_featured_auction(&program_id, &accounts);
+        assert_eq!(result, Err(DaoError::AuctionBidsOutstanding.into()));
+        let result = {
+            let accounts = [
+                bidder.info(true, true),
+                auction.info(false, true),
+                winning_bid.info(false, true),
+                featured.info(false, true),
+                dao.info(false, false),
+                system.info(false, false),
+                config.info(false, false),
+                fee_recipient.info(false, true),
+                treasury.info(false, true),
+            ];
+            process_claim_featured_slot(&program_id, &accounts)
+        };
+        assert_eq!(result, Ok(()));
+        
+        // The bid is split between the fee recipient and the treasury, the rent goes back
+        assert_eq!((fee_recipient.lamports, treasury.lamports), (375, 125));
+        assert_eq!((winning_bid.lamports, bidder.lamports), (0, 700));
+        
+        // The expired listing is replaced by the auctioned period
+        let featured_data = Featured::try_from_slice(&featured.data).unwrap();
+        assert_eq!(
+            (featured_data.starts_at, featured_data.expires_at, featured_data.days),
+            (100, 100 + SECONDS_PER_DAY, 2)
+        );
+        
+        // Once every bid is settled, the auction's rent goes back to its opener
+        let mut stranger = TestAccount::new(Pubkey::new_unique(), system_program::id(), vec![]);
+        let accounts = [auction.info(false, true), stranger.info(false, true)];
+        let result = process_close_featured_auction(&program_id, &accounts);
+        assert_eq!(result, Err(DaoError::Unauthorized.into()));
+        let accounts = [auction.info(false, true), bidder.info(false, true)];
+        process_close_featured_auction(&program_id, &accounts).unwrap();
+        assert_eq!((auction.lamports, bidder.lamports), (0, 750));
+    }
     
-    msg!("Module activation fee: {} lamports (${} at SOL price of ${}.{})", 
-        module_fee,
-        CREATE_DAO_FEE_USD,
-        sol_price_usd / 100,
-        sol_price_usd % 100
-    );
+    #[test]
+    fn test_featured_claim_after_period() {
+        setup_syscall_stubs();
+        let program_id = Pubkey::new_unique();
+        let winner = Pubkey::new_unique();
+        let mut bidder = TestAccount::new(Pubkey::new_unique(), system_program::id(), vec![]);
+        let auction_data = FeaturedAuction {
+            account_type: AccountType::FeaturedAuction,
+            period: 1,
+            slots: 1,
+            days: 1,
+            starts_at: TEST_NOW - SECONDS_PER_DAY,
+            ends_at: TEST_NOW,
+            settled: true,
+            opener: bidder.key,
+            open_bids: 1,
+            leaders: vec![FeaturedBidEntry { dao_id: winner, amount: 500 }],
+        };
+        let mut auction = TestAccount::new(
+            Pubkey::new_unique(),
+            program_id,
+            auction_data.try_to_vec().unwrap(),
+        );
+        let bid_data = FeaturedBid {
+            account_type: AccountType::FeaturedBid,
+            auction: auction.key,
+            dao_id: winner,
+            bidder: bidder.key,
+            amount: 500,
+        };
+        let mut bid =
+            TestAccount::new(Pubkey::new_unique(), program_id, bid_data.try_to_vec().unwrap());
+        bid.lamports = 600;
+        
+        // Winners can't be charged for a period that is already over
+        let mut dao = TestAccount::new(winner, program_id, test_dao().try_to_vec().unwrap());
+        let (treasury, _) = pda::find_treasury_address(&program_id, &winner);
+        let mut treasury = TestAccount::new(treasury, system_program::id(), vec![]);
+        let config_data = test_config();
+        let mut config = config_test_account(&program_id, &config_data);
+        let mut fee_recipient =
+            TestAccount::new(config_data.fee_recipient, system_program::id(), vec![]);
+        let (featured, _) = pda::find_featured_address(&program_id, &winner);
+        let mut featured = TestAccount::new(featured, system_program::id(), vec![]);
+        let mut system = TestAccount::new(system_program::id(), Pubkey::default(), vec![]);
+        let accounts = [
+            bidder.info(true, true),
+            auction.info(false, true),
+            bid.info(false, true),
+            featured.info(false, true),
+            dao.info(false, false),
+            system.info(false, false),
+            config.info(false, false),
+            fee_recipient.info(false, true),
+            treasury.info(false, true),
+        ];
+        let result = process_claim_featured_slot(&program_id, &accounts);
+        assert_eq!(result, Err(DaoError::FeaturedPeriodEnded.into()));
+        assert_eq!((fee_recipient.lamports, treasury.lamports, bid.lamports), (0, 0, 600));
+        
+        // The unclaimed bid is refunded instead, so the auction can be closed
+        let accounts = [
+            bidder.info(false, true),
+            auction.info(false, true),
+            bid.info(false, true),
+        ];
+        process_refund_featured_bid(&program_id, &accounts).unwrap();
+        assert_eq!((bid.lamports, bidder.lamports), (0, 600));
+        let accounts = [auction.info(false, true), bidder.info(false, true)];
+        process_close_featured_auction(&program_id, &accounts).unwrap();
+    }
     
-    // Create module data structure
-    let module_data = Module {
-        authority: *creator_account.key,
-        dao_id,
-        module_type,
-    };
+    #[test]
+    fn test_featured_schedule() {
+        let day = SECONDS_PER_DAY;
+        let listing = |starts_at, expires_at| Featured {
+            account_type: AccountType::Featured,
+            authority: Pubkey::new_unique(),
+            dao_id: Pubkey::new_unique(),
+            days: 1,
+            starts_at,
+            expires_at,
+        };
+        let window = |featured: &Featured| (featured.starts_at, featured.expires_at, featured.days);
+        
+        // Claiming a period after the following one keeps both
+        let mut featured = listing(2 * day, 3 * day);
+        featured.schedule(day / 2, day, 2 * day, 1).unwrap();
+        assert_eq!(window(&featured), (day, 3 * day, 2));
+        featured.schedule(day / 2, 3 * day, 4 * day, 1).unwrap();
+        assert_eq!(window(&featured), (day, 4 * day, 3));
+        
+        // A separate period can't replace a listing that is still running
+        let mut featured = listing(day, 2 * day);
+        assert_eq!(
+            featured.schedule(day, 3 * day, 4 * day, 1),
+            Err(DaoError::FeaturedScheduleConflict.into())
+        );
+        
+        // Periods that are already over leave it as it is
+        featured.schedule(day, 0, day / 2, 1).unwrap();
+        assert_eq!(window(&featured), (day, 2 * day, 2));
+        
+        // Expired listings are replaced
+        featured.schedule(2 * day, 3 * day, 4 * day, 1).unwrap();
+        assert_eq!(window(&featured), (3 * day, 4 * day, 3));
+    }
     
-    // Calculate space required for the module account
-    let module_serialized = module_data.try_to_vec()?;
-    let space = module_serialized.len() as u64;
+    #[test]
+    fn test_featured_tiers() {
+        let tiers = [
+            FeaturedTier { min_days: 7, per_day: 1_500 },
+            FeaturedTier { min_days: 30, per_day: 1_000 },
+            FeaturedTier { min_days: 365, per_day: 500 },
+        ];
+        
+        // Below the first tier the base daily fee applies
+        assert_eq!(calculate_featured_fee_usd_cents(2_000, &tiers, 1), Ok(2_000));
+        assert_eq!(calculate_featured_fee_usd_cents(2_000, &tiers, 6), Ok(12_000));
+        
+        // Each tier starts at its minimum number of days
+        assert_eq!(calculate_featured_fee_usd_cents(2_000, &tiers, 7), Ok(10_500));
+        assert_eq!(calculate_featured_fee_usd_cents(2_000, &tiers, 29), Ok(43_500));
+        assert_eq!(calculate_featured_fee_usd_cents(2_000, &tiers, 30), Ok(30_000));
+        assert_eq!(calculate_featured_fee_usd_cents(2_000, &tiers, 364), Ok(364_000));
+        assert_eq!(calculate_featured_fee_usd_cents(2_000, &tiers, 365), Ok(182_500));
+        assert_eq!(
+            calculate_featured_fee_usd_cents(2_000, &tiers, 0),
+            Err(ProgramError::InvalidInstructionData)
+        );
+        
+        // Tiers must be sorted by duration, within a year
+        let mut config = test_config();
+        config.featured_tiers = tiers.to_vec();
+        assert_eq!(config.validate(), Ok(()));
+        config.featured_tiers.swap(0, 1);
+        assert_eq!(config.validate(), Err(DaoError::InvalidConfig.into()));
+        config.featured_tiers = vec![FeaturedTier { min_days: 366, per_day: 100 }];
+        assert_eq!(config.validate(), Err(DaoError::InvalidConfig.into()));
+    }
     
-    // Calculate the rent required
-    let rent = Rent::get()?;
-    let rent_lamports = rent.minimum_balance(space as usize);
+    #[test]
+    fn test_module_registry() {
+        setup_syscall_stubs();
+        let program_id = Pubkey::new_unique();
+        let mut dao_data = test_dao();
+        let manager = Pubkey::new_unique();
+        dao_data.module_manager = Some(manager);
+        let mut dao =
+            TestAccount::new(Pubkey::new_unique(), program_id, dao_data.try_to_vec().unwrap());
+        let (registry_key, _) = pda::find_module_address(&program_id, &dao.key);
+        let mut registry = ModuleRegistry {
+            account_type: AccountType::Module,
+            dao_id: dao.key,
+            pod_enabled: false,
+            pol_enabled: false,
+        };
+        
+        // Each module can only be enabled once, and disabled while enabled
+        registry.enable(ModuleType::Pod).unwrap();
+        assert!(registry.is_enabled(ModuleType::Pod));
+        assert!(!registry.is_enabled(ModuleType::Pol));
+        assert_eq!(
+            registry.enable(ModuleType::Pod),
+            Err(DaoError::ModuleAlreadyEnabled.into())
+        );
+        assert_eq!(
+            registry.disable(ModuleType::Pol),
+            Err(DaoError::ModuleNotEnabled.into())
+        );
+        
+        // Unknown module types don't deserialize
+        assert!(ModuleType::try_from_slice(&[1]).is_ok());
+        assert!(ModuleType::try_from_slice(&[2]).is_err());
+        
+        // Enabling a module twice is rejected before any fee is charged
+        let mut creator = TestAccount::new(dao_data.authority, system_program::id(), vec![]);
+        let mut stranger = TestAccount::new(Pubkey::new_unique(), system_program::id(), vec![]);
+        let mut manager = TestAccount::new(manager, system_program::id(), vec![]);
+        let mut registry =
+            TestAccount::new(registry_key, program_id, registry.try_to_vec().unwrap());
+        let mut system = TestAccount::new(system_program::id(), Pubkey::default(), vec![]);
+        let mut fee = TestAccount::new(Pubkey::new_unique(), system_program::id(), vec![]);
+        let mut price = TestAccount::new(Pubkey::new_unique(), Pubkey::default(), vec![]);
+        let mut config = config_test_account(&program_id, &test_config());
+        let dao_id = dao.key;
+        let mut enable_pod = |creator: &mut TestAccount, registry: &mut TestAccount| {
+            let accounts = [
+                creator.info(true, true),
+                registry.info(false, true),
+                dao.info(false, false),
+                system.info(false, false),
+                fee.info(false, true),
+                price.info(false, false),
+                config.info(false, false),
+            ];
+            process_modules(&program_id, &accounts, dao_id, ModuleType::Pod, None)
+        };
+        assert_eq!(
+            enable_pod(&mut creator, &mut registry),
+            Err(DaoError::ModuleAlreadyEnabled.into())
+        );
+        
+        // Only the DAO authority and its module manager can manage modules
+        assert_eq!(enable_pod(&mut stranger, &mut registry), Err(DaoError::Unauthorized.into()));
+        let mut dao = TestAccount::new(dao_id, program_id, dao_data.try_to_vec().unwrap());
+        let mut disable_pod = |signer: &mut TestAccount| {
+            let accounts = [
+                signer.info(true, false),
+                registry.info(false, true),
+                dao.info(false, false),
+            ];
+            process_disable_module(&program_id, &accounts, dao_id, ModuleType::Pod)
+        };
+        assert_eq!(disable_pod(&mut stranger), Err(DaoError::Unauthorized.into()));
+        disable_pod(&mut manager).unwrap();
+        assert_eq!(disable_pod(&mut creator), Err(DaoError::ModuleNotEnabled.into()));
+    }
     
-    // Check if creator has enough funds for rent + fee
-    if creator_account.lamports() < rent_lamports + module_fee {
-        return Err(DaoError::InsufficientFunds.into());
+    #[test]
+    fn test_featured_sponsors() {
+        setup_syscall_stubs();
+        let program_id = Pubkey::new_unique();
+        let mut dao_data = test_dao();
+        let mut sponsor = TestAccount::new(Pubkey::new_unique(), system_program::id(), vec![]);
+        assert_eq!(dao_data.check_featured_sponsor(&dao_data.authority), Ok(()));
+        assert_eq!(
+            dao_data.check_featured_sponsor(&sponsor.key),
+            Err(DaoError::Unauthorized.into())
+        );
+        dao_data.allow_featured_sponsors = true;
+        assert_eq!(dao_data.check_featured_sponsor(&sponsor.key), Ok(()));
+        
+        // Sponsors can't opt the DAO in themselves
+        let mut dao =
+            TestAccount::new(Pubkey::new_unique(), program_id, dao_data.try_to_vec().unwrap());
+        let mut system = TestAccount::new(system_program::id(), Pubkey::default(), vec![]);
+        let accounts = [
+            sponsor.info(true, true),
+            dao.info(false, true),
+            system.info(false, false),
+        ];
+        let result = process_update_dao_permissions(&program_id, &accounts, None, true);
+        assert_eq!(result, Err(DaoError::Unauthorized.into()));
+        
+        // Featuring a DAO that doesn't allow sponsors is rejected
+        dao_data.allow_featured_sponsors = false;
+        let mut dao =
+            TestAccount::new(Pubkey::new_unique(), program_id, dao_data.try_to_vec().unwrap());
+        let dao_id = dao.key;
+        let (featured_key, _) = pda::find_featured_address(&program_id, &dao_id);
+        let mut featured = TestAccount::new(featured_key, system_program::id(), vec![]);
+        let mut fee = TestAccount::new(Pubkey::new_unique(), system_program::id(), vec![]);
+        let mut price = TestAccount::new(Pubkey::new_unique(), Pubkey::default(), vec![]);
+        let mut config = config_test_account(&program_id, &test_config());
+        let accounts = [
+            sponsor.info(true, true),
+            featured.info(false, true),
+            dao.info(false, false),
+            system.info(false, false),
+            fee.info(false, true),
+            price.info(false, false),
+            config.info(false, false),
+        ];
+        let result = process_featured(&program_id, &accounts, dao_id, 7, None);
+        assert_eq!(result, Err(DaoError::Unauthorized.into()));
     }
     
-    // Create the module account
-    invoke(
-        &system_instruction::create_account(
-            creator_account.key,
-            module_account.key,
-            rent_lamports,
-            space,
-            program_id,
-        ),
-        &[creator_account.clone(), module_account.clone(), system_program.clone()],
-    )?;
+    #[test]
+    fn test_pods() {
+        setup_syscall_stubs();
+        let program_id = Pubkey::new_unique();
+        let dao_data = test_dao();
+        let mut dao =
+            TestAccount::new(Pubkey::new_unique(), program_id, dao_data.try_to_vec().unwrap());
+        let dao_id = dao.key;
+        let member = Pubkey::new_unique();
+        let mut authority = TestAccount::new(dao_data.authority, system_program::id(), vec![]);
+        let mut system = TestAccount::new(system_program::id(), Pubkey::default(), vec![]);
+        
+        // Pods can only be created once the POD module is enabled
+        let (registry_key, _) = pda::find_module_address(&program_id, &dao_id);
+        let mut registry = TestAccount::new(registry_key, system_program::id(), vec![]);
+        let (pod_key, _) = pda::find_pod_address(&program_id, &dao_id, "Team");
+        let mut pod = TestAccount::new(pod_key, system_program::id(), vec![]);
+        let accounts = [
+            authority.info(true, true),
+            pod.info(false, true),
+            dao.info(false, false),
+            registry.info(false, false),
+            system.info(false, false),
+        ];
+        let result = process_create_pod(&program_id, &accounts, dao_id, "Team".to_string());
+        assert_eq!(result, Err(DaoError::ModuleNotEnabled.into()));
+        
+        // Members are managed by the pod authority, in an account sized for a full pod
+        let registry_data = ModuleRegistry {
+            account_type: AccountType::Module,
+            dao_id,
+            pod_enabled: true,
+            pol_enabled: false,
+        };
+        let mut registry =
+            TestAccount::new(registry_key, program_id, registry_data.try_to_vec().unwrap());
+        let pod_data = Pod {
+            account_type: AccountType::Pod,
+            dao_id,
+            authority: dao_data.authority,
+            name: "Team".to_string(),
+            members: Vec::new(),
+        };
+        let mut data = pod_data.try_to_vec().unwrap();
+        data.resize(pod_data.max_space(), 0);
+        let mut pod = TestAccount::new(pod_key, program_id, data);
+        let mut stranger = TestAccount::new(member, system_program::id(), vec![]);
+        let mut add_member = |signer: &mut TestAccount, pod: &mut TestAccount| {
+            let accounts = [
+                signer.info(true, false),
+                pod.info(false, true),
+                registry.info(false, false),
+            ];
+            process_add_pod_member(&program_id, &accounts, member)
+        };
+        assert_eq!(add_member(&mut stranger, &mut pod), Err(DaoError::Unauthorized.into()));
+        add_member(&mut authority, &mut pod).unwrap();
+        assert_eq!(add_member(&mut authority, &mut pod), Err(DaoError::AlreadyPodMember.into()));
+        
+        // Only members can create proposals for the pod
+        let create_pod_proposal = |creator: Pubkey,
+                                   pod: &mut TestAccount,
+                                   dao: &mut TestAccount,
+                                   registry: &mut TestAccount| {
+            let mut creator = TestAccount::new(creator, system_program::id(), vec![]);
+            let (proposal_key, _) =
+                pda::find_proposal_address(&program_id, &dao_id, &creator.key, "Proposal");
+            // Stands in for the space the system program would allocate
+            let mut proposal = TestAccount::new(proposal_key, system_program::id(), vec![0; 512]);
+            let mut system = TestAccount::new(system_program::id(), Pubkey::default(), vec![]);
+            let mut config = config_test_account(&program_id, &test_config());
+            let accounts = [
+                creator.info(true, true),
+                proposal.info(false, true),
+                dao.info(false, false),
+                system.info(false, false),
+                config.info(false, false),
+                pod.info(false, false),
+                registry.info(false, false),
+            ];
+            process_create_proposal(
+                &program_id,
+                &accounts,
+                "Proposal".to_string(),
+                String::new(),
+                dao_id,
+                pod_key.to_string(),
+                TEST_NOW + 10,
+                TEST_NOW + 100,
+                None,
+                Vec::new(),
+                Vec::new(),
+                false,
+            )
+        };
+        assert_eq!(
+            create_pod_proposal(Pubkey::new_unique(), &mut pod, &mut dao, &mut registry),
+            Err(DaoError::NotPodMember.into())
+        );
+        create_pod_proposal(member, &mut pod, &mut dao, &mut registry).unwrap();
+        
+        // Pods stop taking proposals once the POD module is disabled
+        let disabled_data = ModuleRegistry { pod_enabled: false, ..registry_data };
+        let mut disabled =
+            TestAccount::new(registry_key, program_id, disabled_data.try_to_vec().unwrap());
+        assert_eq!(
+            create_pod_proposal(member, &mut pod, &mut dao, &mut disabled),
+            Err(DaoError::ModuleNotEnabled.into())
+        );
+        
+        // Pods of other DAOs are rejected
+        let mut other_dao =
+            TestAccount::new(Pubkey::new_unique(), program_id, dao_data.try_to_vec().unwrap());
+        let other_dao_id = other_dao.key;
+        let result = {
+            let mut creator = TestAccount::new(member, system_program::id(), vec![]);
+            let mut proposal = TestAccount::new(Pubkey::new_unique(), system_program::id(), vec![]);
+            let mut config = config_test_account(&program_id, &test_config());
+            let accounts = [
+                creator.info(true, true),
+                proposal.info(false, true),
+                other_dao.info(false, false),
+                system.info(false, false),
+                config.info(false, false),
+                pod.info(false, false),
+                registry.info(false, false),
+            ];
+            process_create_proposal(
+                &program_id,
+                &accounts,
+                "Proposal".to_string(),
+                String::new(),
+                other_dao_id,
+                pod_key.to_string(),
+                TEST_NOW + 10,
+                TEST_NOW + 100,
+                None,
+                Vec::new(),
+                Vec::new(),
+                false,
+            )
+        };
+        assert_eq!(result, Err(DaoError::DaoMismatch.into()));
+        
+        // Members can only be removed while the POD module is enabled
+        let mut remove_member = |pod: &mut TestAccount, registry: &mut TestAccount| {
+            let accounts = [
+                authority.info(true, false),
+                pod.info(false, true),
+                registry.info(false, false),
+            ];
+            process_remove_pod_member(&program_id, &accounts, member)
+        };
+        assert_eq!(
+            remove_member(&mut pod, &mut disabled),
+            Err(DaoError::ModuleNotEnabled.into())
+        );
+        
+        // Removed members can no longer propose for the pod
+        remove_member(&mut pod, &mut registry).unwrap();
+        assert_eq!(remove_member(&mut pod, &mut registry), Err(DaoError::NotPodMember.into()));
+        assert_eq!(
+            create_pod_proposal(member, &mut pod, &mut dao, &mut registry),
+            Err(DaoError::NotPodMember.into())
+        );
+    }
     
-    // Transfer fee to fee account
-    invoke(
-        &system_instruction::transfer(
-            creator_account.key,
-            fee_account.key,
-            module_fee,
-        ),
-        &[creator_account.clone(), fee_account.clone(), system_program.clone()],
-    )?;
+    #[test]
+    fn test_reputation_decay() {
+        let mut reputation = Reputation::new(Pubkey::new_unique(), Pubkey::new_unique(), 0);
+        reputation.endorse(0).unwrap();
+        reputation.endorse(0).unwrap();
+        assert_eq!(reputation.score_at(0), 200);
+        
+        // Scores halve every half-life, linearly in between
+        assert_eq!(reputation.score_at(REPUTATION_HALF_LIFE / 2), 150);
+        assert_eq!(reputation.score_at(REPUTATION_HALF_LIFE), 100);
+        assert_eq!(reputation.score_at(2 * REPUTATION_HALF_LIFE), 50);
+        assert_eq!(reputation.score_at(64 * REPUTATION_HALF_LIFE), 0);
+        
+        // New endorsements add to the decayed score
+        reputation.endorse(REPUTATION_HALF_LIFE).unwrap();
+        assert_eq!(reputation.score, 200);
+        assert_eq!(reputation.endorsements_received, 3);
+        
+        // Members can endorse once per cooldown
+        reputation.record_endorsement_given(10).unwrap();
+        assert_eq!(
+            reputation.record_endorsement_given(10 + ENDORSEMENT_COOLDOWN - 1),
+            Err(DaoError::EndorsementRateLimited.into())
+        );
+        reputation.record_endorsement_given(10 + ENDORSEMENT_COOLDOWN).unwrap();
+        
+        // And the same member once per repeat cooldown
+        let mut endorsement =
+            Endorsement::new(Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        endorsement.record(10).unwrap();
+        assert_eq!(
+            endorsement.record(10 + ENDORSEMENT_REPEAT_COOLDOWN - 1),
+            Err(DaoError::EndorsementRateLimited.into())
+        );
+        endorsement.record(10 + ENDORSEMENT_REPEAT_COOLDOWN).unwrap();
+    }
+    
+    #[test]
+    fn test_endorse() {
+        setup_syscall_stubs();
+        let program_id = Pubkey::new_unique();
+        let mut endorser = TestAccount::new(Pubkey::new_unique(), system_program::id(), vec![]);
+        let endorser_key = endorser.key;
+        
+        // The DAO authority can endorse without any reputation of their own
+        let mut dao_data = test_dao();
+        dao_data.authority = endorser_key;
+        let mut dao =
+            TestAccount::new(Pubkey::new_unique(), program_id, dao_data.try_to_vec().unwrap());
+        let dao_id = dao.key;
+        let member = Pubkey::new_unique();
+        let reputation_account = |member: &Pubkey| {
+            let (key, _) = pda::find_reputation_address(&program_id, &dao_id, member);
+            let data = Reputation::new(dao_id, *member, 0).try_to_vec().unwrap();
+            TestAccount::new(key, program_id, data)
+        };
+        let mut endorser_reputation = reputation_account(&endorser.key);
+        let mut member_reputation = reputation_account(&member);
+        let (registry_key, _) = pda::find_module_address(&program_id, &dao_id);
+        let registry_data = |pol_enabled: bool| {
+            ModuleRegistry {
+                account_type: AccountType::Module,
+                dao_id,
+                pod_enabled: false,
+                pol_enabled,
+            }
+            .try_to_vec()
+            .unwrap()
+        };
+        let mut system = TestAccount::new(system_program::id(), Pubkey::default(), vec![]);
+        let (endorsement_key, _) =
+            pda::find_endorsement_address(&program_id, &dao_id, &endorser_key, &member);
+        let endorsement_data = Endorsement::new(dao_id, endorser_key, member);
+        let mut endorsement =
+            TestAccount::new(endorsement_key, program_id, endorsement_data.try_to_vec().unwrap());
+        let mut endorse = |registry: &mut TestAccount, member: Pubkey| {
+            let accounts = [
+                endorser.info(true, true),
+                endorser_reputation.info(false, true),
+                member_reputation.info(false, true),
+                dao.info(false, false),
+                registry.info(false, false),
+                system.info(false, false),
+                endorsement.info(false, true),
+            ];
+            process_endorse(&program_id, &accounts, dao_id, member)
+        };
+        
+        // Endorsements require the POL module
+        let mut registry = TestAccount::new(registry_key, program_id, registry_data(false));
+        assert_eq!(endorse(&mut registry, member), Err(DaoError::ModuleNotEnabled.into()));
+        
+        // Members can't endorse themselves, and only once per cooldown
+        let mut registry = TestAccount::new(registry_key, program_id, registry_data(true));
+        assert_eq!(
+            endorse(&mut registry, endorser_key),
+            Err(DaoError::InvalidEndorsement.into())
+        );
+        endorse(&mut registry, member).unwrap();
+        assert_eq!(
+            endorse(&mut registry, member),
+            Err(DaoError::EndorsementRateLimited.into())
+        );
+        
+        // The endorsement shows up in the member's score
+        let score = reputation_score(
+            &program_id,
+            &member_reputation.info(false, false),
+            &dao_id,
+            &member,
+            TEST_NOW,
+        );
+        assert_eq!(score, Ok(ENDORSEMENT_POINTS));
+        
+        // Members who were never endorsed have no reputation
+        let stranger = Pubkey::new_unique();
+        let (key, _) = pda::find_reputation_address(&program_id, &dao_id, &stranger);
+        let mut unknown = TestAccount::new(key, system_program::id(), vec![]);
+        let score =
+            reputation_score(&program_id, &unknown.info(false, false), &dao_id, &stranger, 0);
+        assert_eq!(score, Ok(0));
+    }
     
-    // Serialize and store the module data
-    module_data.serialize(&mut &mut module_account.data.borrow_mut()[..])?;
+    #[test]
+    fn test_endorse_restrictions() {
+        setup_syscall_stubs();
+        let program_id = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let mut dao_data = test_dao();
+        dao_data.token_address = mint.to_string();
+        let mut dao =
+            TestAccount::new(Pubkey::new_unique(), program_id, dao_data.try_to_vec().unwrap());
+        let dao_id = dao.key;
+        let (registry_key, _) = pda::find_module_address(&program_id, &dao_id);
+        let registry_data = ModuleRegistry {
+            account_type: AccountType::Module,
+            dao_id,
+            pod_enabled: false,
+            pol_enabled: true,
+        };
+        let mut registry =
+            TestAccount::new(registry_key, program_id, registry_data.try_to_vec().unwrap());
+        let mut system = TestAccount::new(system_program::id(), Pubkey::default(), vec![]);
+        let mut endorser = TestAccount::new(Pubkey::new_unique(), system_program::id(), vec![]);
+        let endorser_key = endorser.key;
+        let member = Pubkey::new_unique();
+        let reputation_data = |member: &Pubkey, score: u64, last_endorsed_at: i64| {
+            let mut reputation = Reputation::new(dao_id, *member, TEST_NOW);
+            reputation.score = score;
+            reputation.last_endorsed_at = last_endorsed_at;
+            reputation.try_to_vec().unwrap()
+        };
+        let (key, _) = pda::find_reputation_address(&program_id, &dao_id, &endorser_key);
+        let mut endorser_reputation = TestAccount::new(key, program_id, vec![]);
+        let (key, _) = pda::find_reputation_address(&program_id, &dao_id, &member);
+        let data = reputation_data(&member, 0, 0);
+        let mut member_reputation = TestAccount::new(key, program_id, data);
+        let (key, _) = pda::find_endorsement_address(&program_id, &dao_id, &endorser_key, &member);
+        let data = Endorsement::new(dao_id, endorser_key, member).try_to_vec().unwrap();
+        let mut endorsement = TestAccount::new(key, program_id, data);
+        let mut endorser_tokens = token_test_account(&endorser_key, &mint, 10);
+        let mut endorse = |endorser_reputation: &mut TestAccount, member_tokens: &mut TestAccount| {
+            let accounts = [
+                endorser.info(true, true),
+                endorser_reputation.info(false, true),
+                member_reputation.info(false, true),
+                dao.info(false, false),
+                registry.info(false, false),
+                system.info(false, false),
+                endorsement.info(false, true),
+                endorser_tokens.info(false, false),
+                member_tokens.info(false, false),
+            ];
+            process_endorse(&program_id, &accounts, dao_id, member)
+        };
+        
+        // Endorsed members of token DAOs must hold the governance token
+        endorser_reputation.data = reputation_data(&endorser_key, MIN_ENDORSER_REPUTATION, 0);
+        let mut member_tokens = token_test_account(&member, &mint, 0);
+        assert_eq!(
+            endorse(&mut endorser_reputation, &mut member_tokens),
+            Err(DaoError::NoVotingPower.into())
+        );
+        
+        // Endorsers other than the DAO authority need reputation of their own
+        let mut member_tokens = token_test_account(&member, &mint, 1);
+        endorser_reputation.data = reputation_data(&endorser_key, MIN_ENDORSER_REPUTATION - 1, 0);
+        assert_eq!(
+            endorse(&mut endorser_reputation, &mut member_tokens),
+            Err(DaoError::InsufficientReputation.into())
+        );
+        endorser_reputation.data = reputation_data(&endorser_key, MIN_ENDORSER_REPUTATION, 0);
+        endorse(&mut endorser_reputation, &mut member_tokens).unwrap();
+        
+        // Once the daily cooldown is over, the endorser still can't endorse the same member again
+        let last_endorsed_at = TEST_NOW - ENDORSEMENT_COOLDOWN;
+        endorser_reputation.data =
+            reputation_data(&endorser_key, MIN_ENDORSER_REPUTATION, last_endorsed_at);
+        assert_eq!(
+            endorse(&mut endorser_reputation, &mut member_tokens),
+            Err(DaoError::EndorsementRateLimited.into())
+        );
+        let endorsement = Endorsement::deserialize(&mut &endorsement.data[..]).unwrap();
+        assert_eq!(endorsement.endorsed_at, TEST_NOW);
+    }
     
-    msg!("DAO module activated successfully with ID: {}", module_account.key);
-    Ok(())
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
+    #[test]
+    fn test_reputation_vote_weight() {
+        setup_syscall_stubs();
+        let program_id = Pubkey::new_unique();
+        let dao_id = Pubkey::new_unique();
+        let voter = Pubkey::new_unique();
+        let (registry_key, _) = pda::find_module_address(&program_id, &dao_id);
+        let registry_data = ModuleRegistry {
+            account_type: AccountType::Module,
+            dao_id,
+            pod_enabled: false,
+            pol_enabled: true,
+        };
+        let mut registry =
+            TestAccount::new(registry_key, program_id, registry_data.try_to_vec().unwrap());
+        let (reputation_key, _) = pda::find_reputation_address(&program_id, &dao_id, &voter);
+        let mut reputation_data = Reputation::new(dao_id, voter, TEST_NOW);
+        reputation_data.score = 200;
+        let mut reputation =
+            TestAccount::new(reputation_key, program_id, reputation_data.try_to_vec().unwrap());
+        
+        // Members without reputation have no voting power
+        let mut no_reputation = TestAccount::new(reputation_key, system_program::id(), vec![]);
+        let weight = reputation_vote_weight(
+            &program_id,
+            &registry.info(false, false),
+            &no_reputation.info(false, false),
+            &dao_id,
+            &voter,
+            TEST_NOW,
+            None,
+        );
+        assert_eq!(weight, Err(DaoError::NoVotingPower.into()));
+        
+        // Votes weigh the voter's decayed reputation, optionally capped
+        let mut weight = |registry: &mut TestAccount, now: i64, max_weight: Option<u64>| {
+            reputation_vote_weight(
+                &program_id,
+                &registry.info(false, false),
+                &reputation.info(false, false),
+                &dao_id,
+                &voter,
+                now,
+                max_weight,
+            )
+        };
+        assert_eq!(weight(&mut registry, TEST_NOW, None), Ok(200));
+        assert_eq!(weight(&mut registry, TEST_NOW, Some(150)), Ok(150));
+        assert_eq!(weight(&mut registry, TEST_NOW + REPUTATION_HALF_LIFE, None), Ok(100));
+        
+        // Reputation only counts while the POL module is enabled
+        let registry_data = ModuleRegistry { pol_enabled: false, ..registry_data };
+        let mut registry =
+            TestAccount::new(registry_key, program_id, registry_data.try_to_vec().unwrap());
+        assert_eq!(weight(&mut registry, TEST_NOW, None), Err(DaoError::ModuleNotEnabled.into()));
+    }
     
     #[test]
-    fn test_calculate_fee() {
-        // SOL price = $100.00 (10000 cents)
-        // $20 / $100 = 0.2 SOL = 200_000_000 lamports
-        let fee = calculate_fee_in_lamports(10000).unwrap();
-        assert_eq!(fee, 200_000_000);
+    fn test_quadratic_voting() {
+        assert_eq!([0, 1, 3, 4, 15, 16, 99].map(isqrt), [0, 1, 1, 2, 3, 4, 9]);
+        assert_eq!(isqrt(u64::MAX), u32::MAX as u64);
         
-        // SOL price = $50.00 (5000 cents)
-        // $20 / $50 = 0.4 SOL = 400_000_000 lamports
-        let fee = calculate_fee_in_lamports(5000).unwrap();
-        assert_eq!(fee, 400_000_000);
+        // Options count the square root of the credits spent on them, within the budget
+        let credits = |allocations: &[(u8, u64)]| {
+            VoteChoice::Credits(
+                allocations
+                    .iter()
+                    .map(|&(option, credits)| CreditAllocation { option, credits })
+                    .collect(),
+            )
+        };
+        let mut proposal = test_proposal();
+        let labels = ["A", "B", "C"].map(String::from).to_vec();
+        (proposal.kind, proposal.options) = proposal_options(labels, false).unwrap();
+        proposal.record_vote(&credits(&[(0, 64), (1, 36)]), 100).unwrap();
+        proposal.record_vote(&credits(&[(1, 100)]), 100).unwrap();
+        assert_eq!(proposal.options[0].weight, 8);
+        assert_eq!(proposal.options[1].weight, 16);
+        assert_eq!(proposal.options[1].votes, 2);
+        assert_eq!(
+            proposal.record_vote(&credits(&[(0, 64), (1, 37)]), 100),
+            Err(DaoError::VoiceCreditsExceeded.into())
+        );
         
-        // SOL price = $200.00 (20000 cents)
-        // $20 / $200 = 0.1 SOL = 100_000_000 lamports
-        let fee = calculate_fee_in_lamports(20000).unwrap();
-        assert_eq!(fee, 100_000_000);
+        // Yes/no ballots can't fund both sides
+        let mut yes_no = test_proposal();
+        assert_eq!(
+            yes_no.record_vote(&credits(&[(0, 50), (1, 50)]), 100),
+            Err(DaoError::InvalidVote.into())
+        );
+        yes_no.record_vote(&credits(&[(1, 49)]), 100).unwrap();
+        assert_eq!(yes_no.options[1].weight, 7);
+        for allocations in [&[][..], &[(0, 0)], &[(3, 1)], &[(0, 1), (0, 1)]] {
+            assert_eq!(
+                proposal.record_vote(&credits(allocations), 100),
+                Err(DaoError::InvalidVote.into())
+            );
+        }
+        
+        // Quadratic DAOs only take quadratic ballots and abstentions, and reject empty budgets
+        setup_syscall_stubs();
+        let program_id = Pubkey::new_unique();
+        let mut dao_data = test_dao();
+        dao_data.governance.voting_mode = VotingMode::Quadratic { voice_credits: 100 };
+        assert!(dao_data.governance.validate().is_ok());
+        let proposal_data = test_proposal();
+        let mut dao =
+            TestAccount::new(proposal_data.dao_id, program_id, dao_data.try_to_vec().unwrap());
+        let proposal_data = proposal_data.try_to_vec().unwrap();
+        let mut proposal = TestAccount::new(Pubkey::new_unique(), program_id, proposal_data);
+        let proposal_id = proposal.key.to_string();
+        let mut vote = |choice: VoteChoice, dao: &mut TestAccount| {
+            let voter = Pubkey::new_unique();
+            let (vote_key, _) = pda::find_vote_address(&program_id, &proposal.key, &voter);
+            let mut voter = TestAccount::new(voter, system_program::id(), vec![]);
+            let mut vote_account = TestAccount::new(vote_key, system_program::id(), vec![]);
+            let mut system = TestAccount::new(system_program::id(), Pubkey::default(), vec![]);
+            let mut config = config_test_account(&program_id, &test_config());
+            let accounts = [
+                voter.info(true, true),
+                vote_account.info(false, true),
+                proposal.info(false, true),
+                system.info(false, false),
+                dao.info(false, false),
+                config.info(false, false),
+            ];
+            process_vote(&program_id, &accounts, choice, proposal_id.clone())
+        };
+        assert_eq!(vote(VoteChoice::Option(0), &mut dao), Err(DaoError::InvalidVote.into()));
+        assert_eq!(
+            vote(credits(&[(0, 101)]), &mut dao),
+            Err(DaoError::VoiceCreditsExceeded.into())
+        );
+        dao_data.governance.voting_mode = VotingMode::Token;
+        dao.data = dao_data.try_to_vec().unwrap();
+        assert_eq!(vote(credits(&[(0, 1)]), &mut dao), Err(DaoError::InvalidVote.into()));
+        
+        dao_data.governance.voting_mode = VotingMode::Quadratic { voice_credits: 0 };
+        assert!(dao_data.governance.validate().is_err());
     }
 }
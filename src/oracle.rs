@@ -0,0 +1,148 @@
+//! SOL/USD price read from a Pyth v2 price account.
+//!
+//! Only the fields needed to price fees are decoded, so the program doesn't
+//! depend on the Pyth SDK.
+
+use crate::DaoError;
+use solana_program::{account_info::AccountInfo, msg, program_error::ProgramError, pubkey::Pubkey};
+use std::str::FromStr;
+
+// SOL/USD price accounts accepted by the program, with the Pyth program owning them
+const SOL_USD_PRICE_ACCOUNTS: [(&str, &str); 2] = [
+    // Mainnet
+    (
+        "H6ARHf6YXhGYeQfUzQNGk6rDNnLBQKrenN712K4AQJEG",
+        "FsJ3A3u2vn5cTVofAjvy6y5kwABJAqYWpe4975bi9epH",
+    ),
+    // Devnet
+    (
+        "J83w4HKfqxwcq3BEMMkPFSppX3gqekLyLJBexebFVkix",
+        "gSbePebfvPy7tRqimPoVecS2UsBvYv46ynrzWocc92s",
+    ),
+];
+
+// Maximum age of a price, in seconds
+pub const MAX_PRICE_AGE_SECS: i64 = 60;
+// Maximum confidence interval, as a share of the price in basis points
+pub const MAX_CONFIDENCE_BPS: u64 = 200;
+
+// Pyth v2 price account layout
+const PYTH_MAGIC: u32 = 0xa1b2c3d4;
+const PYTH_VERSION: u32 = 2;
+const PYTH_PRICE_ACCOUNT_TYPE: u32 = 3;
+const PYTH_STATUS_TRADING: u32 = 1;
+const MAGIC_OFFSET: usize = 0;
+const VERSION_OFFSET: usize = 4;
+const ACCOUNT_TYPE_OFFSET: usize = 8;
+const EXPONENT_OFFSET: usize = 20;
+const TIMESTAMP_OFFSET: usize = 96;
+const PRICE_OFFSET: usize = 208;
+const CONFIDENCE_OFFSET: usize = 216;
+const STATUS_OFFSET: usize = 224;
+const PRICE_ACCOUNT_SIZE: usize = 3312;
+
+/// Aggregate price published in a Pyth price account
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PythPrice {
+    pub price: i64,
+    pub conf: u64,
+    pub expo: i32,
+    pub publish_time: i64,
+}
+
+impl PythPrice {
+    /// Decode the aggregate price of a Pyth v2 price account
+    pub fn unpack(data: &[u8]) -> Result<Self, ProgramError> {
+        if data.len() < PRICE_ACCOUNT_SIZE
+            || read_u32(data, MAGIC_OFFSET) != PYTH_MAGIC
+            || read_u32(data, VERSION_OFFSET) != PYTH_VERSION
+            || read_u32(data, ACCOUNT_TYPE_OFFSET) != PYTH_PRICE_ACCOUNT_TYPE
+        {
+            return Err(DaoError::InvalidPriceAccount.into());
+        }
+
+        // Halted or unknown feeds don't have a usable aggregate price
+        if read_u32(data, STATUS_OFFSET) != PYTH_STATUS_TRADING {
+            return Err(DaoError::InvalidSolPrice.into());
+        }
+
+        Ok(Self {
+            price: read_i64(data, PRICE_OFFSET),
+            conf: read_u64(data, CONFIDENCE_OFFSET),
+            expo: read_u32(data, EXPONENT_OFFSET) as i32,
+            publish_time: read_i64(data, TIMESTAMP_OFFSET),
+        })
+    }
+
+    /// Price converted to USD cents, after checking its age and confidence
+    pub fn usd_cents(&self, now: i64) -> Result<u64, ProgramError> {
+        if now.saturating_sub(self.publish_time) > MAX_PRICE_AGE_SECS {
+            return Err(DaoError::StalePrice.into());
+        }
+        if self.price <= 0 {
+            return Err(DaoError::InvalidSolPrice.into());
+        }
+
+        let price = self.price as u128;
+        if self.conf as u128 * 10_000 > price * MAX_CONFIDENCE_BPS as u128 {
+            return Err(DaoError::PriceConfidenceTooWide.into());
+        }
+
+        // price * 10^expo dollars = price * 10^(expo + 2) cents
+        let scale = self.expo.checked_add(2).ok_or(DaoError::InvalidSolPrice)?;
+        let factor = 10u128
+            .checked_pow(scale.unsigned_abs())
+            .ok_or(DaoError::InvalidSolPrice)?;
+        let cents = if scale >= 0 {
+            price.checked_mul(factor).ok_or(DaoError::InvalidSolPrice)?
+        } else {
+            price / factor
+        };
+
+        u64::try_from(cents).map_err(|_| DaoError::InvalidSolPrice.into())
+    }
+}
+
+/// Read the SOL/USD price in cents from one of the accepted Pyth price accounts
+pub fn sol_price_usd_cents(price_account: &AccountInfo, now: i64) -> Result<u64, ProgramError> {
+    let accepted = SOL_USD_PRICE_ACCOUNTS.iter().any(|(feed, owner)| {
+        *price_account.key == Pubkey::from_str(feed).unwrap()
+            && *price_account.owner == Pubkey::from_str(owner).unwrap()
+    });
+    if !accepted {
+        msg!("Price account {} is not an accepted SOL/USD feed", price_account.key);
+        return Err(DaoError::InvalidPriceAccount.into());
+    }
+
+    PythPrice::unpack(&price_account.data.borrow())?.usd_cents(now)
+}
+
+fn read_u32(data: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap())
+}
+
+fn read_u64(data: &[u8], offset: usize) -> u64 {
+    u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap())
+}
+
+fn read_i64(data: &[u8], offset: usize) -> i64 {
+    i64::from_le_bytes(data[offset..offset + 8].try_into().unwrap())
+}
+
+/// Mock SOL/USD price account used by the tests: `(key, owner, data)`
+#[cfg(test)]
+pub(crate) fn mock_sol_usd_price_account(price: &PythPrice) -> (Pubkey, Pubkey, Vec<u8>) {
+    let mut data = vec![0; PRICE_ACCOUNT_SIZE];
+    data[MAGIC_OFFSET..MAGIC_OFFSET + 4].copy_from_slice(&PYTH_MAGIC.to_le_bytes());
+    data[VERSION_OFFSET..VERSION_OFFSET + 4].copy_from_slice(&PYTH_VERSION.to_le_bytes());
+    data[ACCOUNT_TYPE_OFFSET..ACCOUNT_TYPE_OFFSET + 4]
+        .copy_from_slice(&PYTH_PRICE_ACCOUNT_TYPE.to_le_bytes());
+    data[EXPONENT_OFFSET..EXPONENT_OFFSET + 4].copy_from_slice(&price.expo.to_le_bytes());
+    data[TIMESTAMP_OFFSET..TIMESTAMP_OFFSET + 8].copy_from_slice(&price.publish_time.to_le_bytes());
+    data[PRICE_OFFSET..PRICE_OFFSET + 8].copy_from_slice(&price.price.to_le_bytes());
+    data[CONFIDENCE_OFFSET..CONFIDENCE_OFFSET + 8].copy_from_slice(&price.conf.to_le_bytes());
+    data[STATUS_OFFSET..STATUS_OFFSET + 4].copy_from_slice(&PYTH_STATUS_TRADING.to_le_bytes());
+
+    let (feed, owner) = SOL_USD_PRICE_ACCOUNTS[0];
+    (Pubkey::from_str(feed).unwrap(), Pubkey::from_str(owner).unwrap(), data)
+}
@@ -0,0 +1,112 @@
+//! Program derived addresses for every account type owned by the DAO program.
+//!
+//! Clients can use the `find_*_address` helpers to locate accounts without
+//! having to index the program.
+
+use solana_program::{hash::hash, pubkey::Pubkey};
+
+// Seed prefixes for each account type
+pub const DAO_SEED: &[u8] = b"dao";
+pub const PROPOSAL_SEED: &[u8] = b"proposal";
+pub const VOTE_SEED: &[u8] = b"vote";
+pub const FEATURED_SEED: &[u8] = b"featured";
+pub const MODULE_SEED: &[u8] = b"module";
+pub const TREASURY_SEED: &[u8] = b"treasury";
+pub const CONFIG_SEED: &[u8] = b"config";
+pub const AUCTION_SEED: &[u8] = b"auction";
+pub const BID_SEED: &[u8] = b"bid";
+pub const POD_SEED: &[u8] = b"pod";
+pub const REPUTATION_SEED: &[u8] = b"reputation";
+pub const ENDORSEMENT_SEED: &[u8] = b"endorsement";
+
+/// Hash a free-form string into a fixed size seed so names longer than
+/// `MAX_SEED_LEN` can still be used to derive an address
+pub fn name_seed(name: &str) -> [u8; 32] {
+    hash(name.as_bytes()).to_bytes()
+}
+
+/// DAO account: `["dao", authority, hash(name)]`
+pub fn find_dao_address(program_id: &Pubkey, authority: &Pubkey, name: &str) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[DAO_SEED, authority.as_ref(), &name_seed(name)],
+        program_id,
+    )
+}
+
+/// Proposal account: `["proposal", dao, authority, hash(name)]`
+pub fn find_proposal_address(
+    program_id: &Pubkey,
+    dao: &Pubkey,
+    authority: &Pubkey,
+    name: &str,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[PROPOSAL_SEED, dao.as_ref(), authority.as_ref(), &name_seed(name)],
+        program_id,
+    )
+}
+
+/// Vote account: `["vote", proposal, voter]`
+pub fn find_vote_address(program_id: &Pubkey, proposal: &Pubkey, voter: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[VOTE_SEED, proposal.as_ref(), voter.as_ref()], program_id)
+}
+
+/// Featured account: `["featured", dao]`
+pub fn find_featured_address(program_id: &Pubkey, dao: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[FEATURED_SEED, dao.as_ref()], program_id)
+}
+
+/// Module registry: `["module", dao]`
+///
+/// One account per DAO, recording which modules the DAO has enabled.
+pub fn find_module_address(program_id: &Pubkey, dao: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[MODULE_SEED, dao.as_ref()], program_id)
+}
+
+/// DAO treasury: `["treasury", dao]`
+///
+/// System-owned account holding the DAO's lamports and owning its associated
+/// token accounts. Only the program can sign for it.
+pub fn find_treasury_address(program_id: &Pubkey, dao: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[TREASURY_SEED, dao.as_ref()], program_id)
+}
+
+/// Global program config: `["config"]`
+pub fn find_config_address(program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[CONFIG_SEED], program_id)
+}
+
+/// Featured slot auction: `["auction", period]`, with the period index in little endian
+pub fn find_auction_address(program_id: &Pubkey, period: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[AUCTION_SEED, &period.to_le_bytes()], program_id)
+}
+
+/// Featured slot bid: `["bid", auction, dao]`
+///
+/// Holds the bid's escrowed lamports on top of its rent.
+pub fn find_bid_address(program_id: &Pubkey, auction: &Pubkey, dao: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[BID_SEED, auction.as_ref(), dao.as_ref()], program_id)
+}
+
+/// Pod account: `["pod", dao, hash(name)]`
+pub fn find_pod_address(program_id: &Pubkey, dao: &Pubkey, name: &str) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[POD_SEED, dao.as_ref(), &name_seed(name)], program_id)
+}
+
+/// Member reputation: `["reputation", dao, member]`
+pub fn find_reputation_address(program_id: &Pubkey, dao: &Pubkey, member: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[REPUTATION_SEED, dao.as_ref(), member.as_ref()], program_id)
+}
+
+/// Endorsement of a member by another member: `["endorsement", dao, endorser, member]`
+pub fn find_endorsement_address(
+    program_id: &Pubkey,
+    dao: &Pubkey,
+    endorser: &Pubkey,
+    member: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[ENDORSEMENT_SEED, dao.as_ref(), endorser.as_ref(), member.as_ref()],
+        program_id,
+    )
+}